@@ -0,0 +1,347 @@
+//! Derive macros for the `pinocchio` crate.
+//!
+//! The macros are re-exported by `pinocchio` when its `derive` feature is enabled,
+//! and the generated code refers to items of the `pinocchio` crate.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Fields, GenericParam, Lifetime, Meta, NestedMeta,
+};
+
+/// Derive the `Pod` trait for a struct.
+///
+/// The struct must be `#[repr(C)]` (or `#[repr(transparent)]`) and not generic. The
+/// generated code asserts at compile time that every field is `Pod`, that the struct
+/// has an alignment of 1 and that it has no padding.
+#[proc_macro_derive(Pod)]
+pub fn derive_pod(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_pod(&input) {
+        Ok(output) => output,
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Generate the `Pod` implementation and layout assertions of `input`.
+fn expand_pod(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`Pod` can not be derived for generic types",
+        ));
+    }
+
+    if !has_c_repr(input)? {
+        return Err(Error::new_spanned(
+            name,
+            "`Pod` requires `#[repr(C)]` or `#[repr(transparent)]`",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                "`Pod` can only be derived for structs",
+            ))
+        }
+    };
+    let field_types = fields.iter().map(|field| &field.ty);
+    let size_types = field_types.clone();
+
+    Ok(quote! {
+        unsafe impl ::pinocchio::pod::Pod for #name {}
+
+        const _: () = {
+            fn assert_pod<T: ::pinocchio::pod::Pod>() {}
+
+            #[allow(dead_code)]
+            fn assert_fields() {
+                #( assert_pod::<#field_types>(); )*
+            }
+
+            assert!(
+                ::core::mem::align_of::<#name>() == 1,
+                concat!("`", stringify!(#name), "` must have an alignment of 1")
+            );
+            assert!(
+                ::core::mem::size_of::<#name>() == 0 #( + ::core::mem::size_of::<#size_types>() )*,
+                concat!("`", stringify!(#name), "` must not have padding")
+            );
+        };
+    }
+    .into())
+}
+
+/// Derive the `InstructionData` trait for a struct or an enum.
+///
+/// Structs encode their fields in order. Enums encode a `u8` discriminator followed
+/// by the fields of the variant, where the discriminator is the value of the variant
+/// discriminant (its index by default). The generated code asserts at compile time
+/// that the discriminants fit in a `u8` and are distinct. The type may have at most
+/// one lifetime parameter, which is the lifetime of the instruction data its fields
+/// borrow from.
+#[proc_macro_derive(InstructionData)]
+pub fn derive_instruction_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_instruction_data(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Generate the `InstructionData` implementation of `input`.
+fn expand_instruction_data(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let mut lifetimes = input.generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(lifetime) => Ok(&lifetime.lifetime),
+        _ => Err(Error::new_spanned(
+            param,
+            "`InstructionData` can not be derived for types with type or const parameters",
+        )),
+    });
+
+    let (lifetime, generics) = match lifetimes.next().transpose()? {
+        Some(lifetime) => {
+            if let Some(other) = lifetimes.next().transpose()? {
+                return Err(Error::new_spanned(
+                    other,
+                    "`InstructionData` can not be derived for types with more than one lifetime",
+                ));
+            }
+            (lifetime.clone(), quote!(<#lifetime>))
+        }
+        None => (
+            Lifetime::new("'__data", proc_macro2::Span::call_site()),
+            quote!(),
+        ),
+    };
+
+    let mut assertions = TokenStream2::new();
+    let (max_len, pack, unpack) = match &input.data {
+        Data::Struct(data) => {
+            let max_len = max_len(&data.fields, &lifetime);
+            let (pattern, pack) = pack_fields(&data.fields);
+            let unpack = unpack_fields(&data.fields);
+
+            (
+                max_len,
+                quote! {
+                    let Self #pattern = self;
+                    #pack
+                },
+                quote!(::core::result::Result::Ok(Self #unpack)),
+            )
+        }
+        Data::Enum(data) => {
+            let mut base = quote!(0);
+            let mut offset = 0usize;
+            let mut discriminants = Vec::new();
+            let mut lens = TokenStream2::new();
+            let mut pack = TokenStream2::new();
+            let mut unpack = TokenStream2::new();
+
+            for variant in &data.variants {
+                // implicit discriminants are one more than the previous one
+                if let Some((_, expr)) = &variant.discriminant {
+                    base = quote!(#expr);
+                    offset = 0;
+                }
+                let value = quote!(((#base) as i128 + #offset as i128));
+                let discriminant = quote!((#value as u8));
+                discriminants.push(value);
+                offset += 1;
+
+                let ident = &variant.ident;
+                let len = max_len(&variant.fields, &lifetime);
+                let (pattern, pack_variant) = pack_fields(&variant.fields);
+                let unpack_variant = unpack_fields(&variant.fields);
+
+                lens.extend(quote! {
+                    if #len > max {
+                        max = #len;
+                    }
+                });
+                pack.extend(quote! {
+                    Self::#ident #pattern => {
+                        writer.write_u8(#discriminant)?;
+                        #pack_variant
+                    }
+                });
+                unpack.extend(quote! {
+                    if discriminator == #discriminant {
+                        return ::core::result::Result::Ok(Self::#ident #unpack_variant);
+                    }
+                });
+            }
+
+            assertions = quote! {
+                const _: () = {
+                    const DISCRIMINANTS: &[i128] = &[#( #discriminants ),*];
+
+                    let mut i = 0;
+                    while i < DISCRIMINANTS.len() {
+                        assert!(
+                            DISCRIMINANTS[i] >= 0 && DISCRIMINANTS[i] <= u8::MAX as i128,
+                            concat!("the discriminants of `", stringify!(#name), "` must fit in a `u8`")
+                        );
+                        let mut j = i + 1;
+                        while j < DISCRIMINANTS.len() {
+                            assert!(
+                                DISCRIMINANTS[i] != DISCRIMINANTS[j],
+                                concat!("the discriminants of `", stringify!(#name), "` must be distinct")
+                            );
+                            j += 1;
+                        }
+                        i += 1;
+                    }
+                };
+            };
+
+            (
+                quote! {{
+                    let mut max = 0;
+                    #lens
+                    1 + max
+                }},
+                quote! {
+                    match self {
+                        #pack
+                    }
+                },
+                quote! {
+                    let discriminator = reader.read_u8()?;
+                    #unpack
+                    ::core::result::Result::Err(
+                        ::pinocchio::program_error::ProgramError::InvalidInstructionData,
+                    )
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                name,
+                "`InstructionData` can not be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        #assertions
+
+        impl<#lifetime> ::pinocchio::instruction_data::InstructionData<#lifetime> for #name #generics {
+            const MAX_LEN: usize = #max_len;
+
+            #[inline]
+            #[allow(unused_variables)]
+            fn pack(
+                &self,
+                writer: &mut ::pinocchio::instruction_data::DataWriter,
+            ) -> ::core::result::Result<(), ::pinocchio::program_error::ProgramError> {
+                #pack
+                ::core::result::Result::Ok(())
+            }
+
+            #[inline]
+            #[allow(unused_variables)]
+            fn unpack(
+                reader: &mut ::pinocchio::instruction_data::DataReader<#lifetime>,
+            ) -> ::core::result::Result<Self, ::pinocchio::program_error::ProgramError> {
+                #unpack
+            }
+        }
+    })
+}
+
+/// Generate the sum of the maximum lengths of `fields`.
+fn max_len(fields: &Fields, lifetime: &Lifetime) -> TokenStream2 {
+    let types = fields.iter().map(|field| &field.ty);
+
+    quote! {
+        (0 #( + <#types as ::pinocchio::instruction_data::InstructionData<#lifetime>>::MAX_LEN )*)
+    }
+}
+
+/// Return the identifiers bound to `fields` when destructuring them.
+fn bindings(fields: &Fields) -> impl Iterator<Item = syn::Ident> + '_ {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, _)| format_ident!("field_{}", index))
+}
+
+/// Generate the pattern destructuring `fields` and the code packing them.
+fn pack_fields(fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|field| &field.ident);
+            let bindings = bindings(fields);
+            quote!({ #( #idents: #bindings ),* })
+        }
+        Fields::Unnamed(_) => {
+            let bindings = bindings(fields);
+            quote!(( #( #bindings ),* ))
+        }
+        Fields::Unit => quote!(),
+    };
+    let bindings = bindings(fields);
+
+    (
+        pattern,
+        quote! {
+            #( ::pinocchio::instruction_data::InstructionData::pack(#bindings, writer)?; )*
+        },
+    )
+}
+
+/// Generate the constructor of `fields` unpacking them in order.
+fn unpack_fields(fields: &Fields) -> TokenStream2 {
+    let unpack = quote!(::pinocchio::instruction_data::InstructionData::unpack(
+        reader
+    )?);
+
+    match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|field| &field.ident);
+            quote!({ #( #idents: #unpack ),* })
+        }
+        Fields::Unnamed(unnamed) => {
+            let unpack = unnamed.unnamed.iter().map(|_| &unpack);
+            quote!(( #( #unpack ),* ))
+        }
+        Fields::Unit => quote!(),
+    }
+}
+
+/// Return whether `input` has a `#[repr(C)]` or `#[repr(transparent)]` attribute.
+fn has_c_repr(input: &DeriveInput) -> Result<bool, Error> {
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            let found = list.nested.iter().any(|nested| {
+                matches!(
+                    nested,
+                    NestedMeta::Meta(Meta::Path(path))
+                        if path.is_ident("C") || path.is_ident("transparent")
+                )
+            });
+
+            if found {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
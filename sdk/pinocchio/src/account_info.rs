@@ -9,7 +9,12 @@ use core::{
 #[cfg(target_os = "solana")]
 use crate::syscalls::sol_memset_;
 
-use crate::{program_error::ProgramError, pubkey::Pubkey, ProgramResult};
+use crate::{
+    pod::{check_account_data, AccountLoad},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
 
 /// Maximum number of bytes a program may add to an account during a
 /// single top-level instruction.
@@ -327,6 +332,78 @@ impl AccountInfo {
         })
     }
 
+    /// Loads the account data as a `T`, failing if the account is not owned by
+    /// `T::OWNER`, the data does not hold a `T` or the data is already mutable
+    /// borrowed.
+    ///
+    /// Returns [`ProgramError::InvalidAccountOwner`] if the account is not owned by
+    /// `T::OWNER`, and [`ProgramError::InvalidAccountData`] if the length or the
+    /// discriminator of the data do not match `T`.
+    #[inline]
+    pub fn load<T: AccountLoad>(&self) -> Result<Ref<T>, ProgramError> {
+        if !self.is_owned_by(&T::OWNER) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let data = self.try_borrow_data()?;
+        check_account_data::<T>(&data)?;
+        // SAFETY: The account data was validated.
+        Ok(Ref::map(data, |data| unsafe {
+            T::from_account_data_unchecked(data)
+        }))
+    }
+
+    /// Loads the account data as a mutable `T`, failing if the account is not owned
+    /// by `T::OWNER`, the data does not hold a `T` or the data is already borrowed.
+    ///
+    /// Returns [`ProgramError::InvalidAccountOwner`] if the account is not owned by
+    /// `T::OWNER`, and [`ProgramError::InvalidAccountData`] if the length or the
+    /// discriminator of the data do not match `T`.
+    #[inline]
+    pub fn load_mut<T: AccountLoad>(&self) -> Result<RefMut<T>, ProgramError> {
+        if !self.is_owned_by(&T::OWNER) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let data = self.try_borrow_mut_data()?;
+        check_account_data::<T>(&data)?;
+        // SAFETY: The account data was validated.
+        Ok(RefMut::map(data, |data| unsafe {
+            T::from_account_data_mut_unchecked(data)
+        }))
+    }
+
+    /// Loads the account data as a `T`, without performing the borrow check.
+    ///
+    /// This method performs the same owner and data validation as [`Self::load`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn load_unchecked<T: AccountLoad>(&self) -> Result<&T, ProgramError> {
+        if !self.is_owned_by(&T::OWNER) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        T::from_account_data(self.borrow_data_unchecked())
+    }
+
+    /// Loads the account data as a mutable `T`, without performing the borrow check.
+    ///
+    /// This method performs the same owner and data validation as [`Self::load_mut`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data mutably – e.g.,
+    /// there are no active borrows of the account data.
+    #[allow(clippy::mut_from_ref)]
+    #[inline]
+    pub unsafe fn load_mut_unchecked<T: AccountLoad>(&self) -> Result<&mut T, ProgramError> {
+        if !self.is_owned_by(&T::OWNER) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        T::from_account_data_mut(self.borrow_mut_data_unchecked())
+    }
+
     /// Checks if it is possible to get a read-only reference to the data field, failing
     /// if the field is already mutable borrowed or if 7 borrows already exist.
     #[inline(always)]
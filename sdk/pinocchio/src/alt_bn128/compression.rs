@@ -0,0 +1,105 @@
+//! Compression and decompression of `alt_bn128` points.
+//!
+//! A compressed point holds only its `x` coordinate, with the two most significant
+//! bits of the first byte encoding the sign of `y` and whether the point is the
+//! point at infinity.
+
+use super::{G1Point, G2Point, FIELD_SIZE, G1_POINT_SIZE, G2_POINT_SIZE};
+use crate::program_error::ProgramError;
+
+/// Size of a compressed G1 point in bytes.
+pub const G1_COMPRESSED_POINT_SIZE: usize = FIELD_SIZE;
+
+/// Size of a compressed G2 point in bytes.
+pub const G2_COMPRESSED_POINT_SIZE: usize = FIELD_SIZE * 2;
+
+/// Identifier of the G1 compression operation.
+pub const G1_COMPRESS: u64 = 0;
+
+/// Identifier of the G1 decompression operation.
+pub const G1_DECOMPRESS: u64 = 1;
+
+/// Identifier of the G2 compression operation.
+pub const G2_COMPRESS: u64 = 2;
+
+/// Identifier of the G2 decompression operation.
+pub const G2_DECOMPRESS: u64 = 3;
+
+/// A compressed point of the G1 group.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct G1CompressedPoint(pub [u8; G1_COMPRESSED_POINT_SIZE]);
+
+/// A compressed point of the G2 group.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct G2CompressedPoint(pub [u8; G2_COMPRESSED_POINT_SIZE]);
+
+/// Performs the compression operation `op` on `input`, writing the result to `result`.
+#[inline]
+fn compression_op(op: u64, input: &[u8], result: &mut [u8]) -> Result<(), ProgramError> {
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        crate::syscalls::sol_alt_bn128_compression(
+            op,
+            input.as_ptr(),
+            input.len() as u64,
+            result.as_mut_ptr(),
+        )
+    };
+
+    #[cfg(not(target_os = "solana"))]
+    let result = unsafe {
+        crate::host::sol_alt_bn128_compression(
+            op,
+            input.as_ptr(),
+            input.len() as u64,
+            result.as_mut_ptr(),
+        )
+    };
+
+    match result {
+        crate::SUCCESS => Ok(()),
+        _ => Err(ProgramError::InvalidArgument),
+    }
+}
+
+/// Compresses a G1 point.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the point is invalid.
+#[inline]
+pub fn compress_g1(point: &G1Point) -> Result<G1CompressedPoint, ProgramError> {
+    let mut result = G1CompressedPoint([0; G1_COMPRESSED_POINT_SIZE]);
+    compression_op(G1_COMPRESS, &point.0, &mut result.0)?;
+    Ok(result)
+}
+
+/// Decompresses a G1 point.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the compressed point is invalid.
+#[inline]
+pub fn decompress_g1(point: &G1CompressedPoint) -> Result<G1Point, ProgramError> {
+    let mut result = G1Point([0; G1_POINT_SIZE]);
+    compression_op(G1_DECOMPRESS, &point.0, &mut result.0)?;
+    Ok(result)
+}
+
+/// Compresses a G2 point.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the point is invalid.
+#[inline]
+pub fn compress_g2(point: &G2Point) -> Result<G2CompressedPoint, ProgramError> {
+    let mut result = G2CompressedPoint([0; G2_COMPRESSED_POINT_SIZE]);
+    compression_op(G2_COMPRESS, &point.0, &mut result.0)?;
+    Ok(result)
+}
+
+/// Decompresses a G2 point.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the compressed point is invalid.
+#[inline]
+pub fn decompress_g2(point: &G2CompressedPoint) -> Result<G2Point, ProgramError> {
+    let mut result = G2Point([0; G2_POINT_SIZE]);
+    compression_op(G2_DECOMPRESS, &point.0, &mut result.0)?;
+    Ok(result)
+}
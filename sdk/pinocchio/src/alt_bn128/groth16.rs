@@ -0,0 +1,529 @@
+//! Groth16 proof verification over the `alt_bn128` curve.
+//!
+//! The verifier does not allocate: the [`VerifyingKey`] holds references to its
+//! points, so it can be declared as a constant or read zero-copy from account
+//! data, and a [`Proof`] can be read zero-copy from instruction data. All points
+//! use the encoding described in the [parent module](super); public inputs are
+//! 32-byte big-endian integers smaller than the scalar field modulus.
+//!
+//! # Compute units
+//!
+//! Preparing the public inputs costs one multiplication (3,840 CUs) and one
+//! addition (334 CUs) per non-zero public input. The final check is a pairing of
+//! four elements, which costs 72,727 CUs. Verifying a proof with `n` public inputs
+//! therefore costs approximately `72,727 + 4,174 * n` CUs, e.g., about 81,000 CUs
+//! for two public inputs, plus a small overhead to copy the points.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use pinocchio::{
+//!     account_info::AccountInfo,
+//!     alt_bn128::groth16::{Proof, VerifyingKey},
+//!     program_error::ProgramError,
+//!     ProgramResult,
+//! };
+//!
+//! fn verify(
+//!     verifying_key_account: &AccountInfo,
+//!     instruction_data: &[u8],
+//!     public_input: [u8; 32],
+//! ) -> ProgramResult {
+//!     let data = verifying_key_account.try_borrow_data()?;
+//!     let verifying_key = VerifyingKey::from_bytes(&data)?;
+//!     let proof = Proof::from_bytes(&instruction_data[..Proof::LEN])?;
+//!
+//!     if !verifying_key.verify(proof, &[public_input])? {
+//!         return Err(ProgramError::InvalidInstructionData);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use super::{
+    add, multiply, pairing, G1Point, G2Point, PairingElement, FIELD_SIZE, G1_POINT_SIZE,
+    G2_POINT_SIZE,
+};
+use crate::program_error::ProgramError;
+
+/// Modulus of the base field, in big-endian.
+const BASE_FIELD_MODULUS: [u8; FIELD_SIZE] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// Modulus of the scalar field, in big-endian.
+const SCALAR_FIELD_MODULUS: [u8; FIELD_SIZE] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// A Groth16 proof.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Proof {
+    /// The `A` point of the proof.
+    pub a: G1Point,
+
+    /// The `B` point of the proof.
+    pub b: G2Point,
+
+    /// The `C` point of the proof.
+    pub c: G1Point,
+}
+
+impl Proof {
+    /// Length of a serialized proof in bytes.
+    pub const LEN: usize = G1_POINT_SIZE * 2 + G2_POINT_SIZE;
+
+    /// Returns a reference to the proof serialized as `a || b || c`.
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if `bytes` is not
+    /// [`Proof::LEN`] bytes long.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        // SAFETY: `Proof` has alignment 1, no padding and `bytes` has the correct length.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+}
+
+/// A Groth16 verifying key.
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyingKey<'a> {
+    /// The `alpha` point in G1.
+    pub alpha_g1: &'a G1Point,
+
+    /// The `beta` point in G2.
+    pub beta_g2: &'a G2Point,
+
+    /// The `gamma` point in G2.
+    pub gamma_g2: &'a G2Point,
+
+    /// The `delta` point in G2.
+    pub delta_g2: &'a G2Point,
+
+    /// The points used to prepare the public inputs, one more than the number of
+    /// public inputs.
+    pub ic: &'a [G1Point],
+}
+
+impl<'a> VerifyingKey<'a> {
+    /// Length in bytes of the fixed part of a serialized verifying key.
+    pub const HEADER_LEN: usize = G1_POINT_SIZE + G2_POINT_SIZE * 3;
+
+    /// Reads a verifying key serialized as `alpha_g1 || beta_g2 || gamma_g2 ||
+    /// delta_g2 || ic`, where `ic` is a non-empty sequence of G1 points.
+    ///
+    /// Returns [`ProgramError::InvalidAccountData`] if `bytes` does not hold a
+    /// verifying key.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        if bytes.len() < Self::HEADER_LEN + G1_POINT_SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let ic = &bytes[Self::HEADER_LEN..];
+        let remainder = ic.len() % G1_POINT_SIZE;
+        if remainder != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // SAFETY: Points have alignment 1 and the length of `bytes` was validated.
+        unsafe {
+            let ptr = bytes.as_ptr();
+            Ok(Self {
+                alpha_g1: &*(ptr as *const G1Point),
+                beta_g2: &*(ptr.add(G1_POINT_SIZE) as *const G2Point),
+                gamma_g2: &*(ptr.add(G1_POINT_SIZE + G2_POINT_SIZE) as *const G2Point),
+                delta_g2: &*(ptr.add(G1_POINT_SIZE + G2_POINT_SIZE * 2) as *const G2Point),
+                ic: core::slice::from_raw_parts(
+                    ic.as_ptr() as *const G1Point,
+                    ic.len() / G1_POINT_SIZE,
+                ),
+            })
+        }
+    }
+
+    /// Returns the number of public inputs expected by the verifying key.
+    #[inline(always)]
+    pub fn public_inputs_len(&self) -> usize {
+        self.ic.len().saturating_sub(1)
+    }
+
+    /// Computes the linear combination of the `ic` points with the public inputs.
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if the number of public inputs does
+    /// not match the verifying key or any public input is not smaller than the
+    /// scalar field modulus.
+    pub fn prepare_inputs(
+        &self,
+        public_inputs: &[[u8; FIELD_SIZE]],
+    ) -> Result<G1Point, ProgramError> {
+        let (first, ic) = self.ic.split_first().ok_or(ProgramError::InvalidArgument)?;
+
+        if public_inputs.len() != ic.len() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut prepared = *first;
+
+        for (input, point) in public_inputs.iter().zip(ic.iter()) {
+            if !is_less_than(input, &SCALAR_FIELD_MODULUS) {
+                return Err(ProgramError::InvalidArgument);
+            }
+            // multiplying by zero results in the identity, which does not change
+            // the accumulated point
+            if input.iter().any(|byte| *byte != 0) {
+                prepared = add(&prepared, &multiply(point, input)?)?;
+            }
+        }
+
+        Ok(prepared)
+    }
+
+    /// Verifies a proof for the given public inputs.
+    ///
+    /// Returns `Ok(false)` if the proof is not valid, [`ProgramError::InvalidArgument`]
+    /// if the number of public inputs does not match the verifying key, any public
+    /// input is not smaller than the scalar field modulus or any point is invalid.
+    pub fn verify(
+        &self,
+        proof: &Proof,
+        public_inputs: &[[u8; FIELD_SIZE]],
+    ) -> Result<bool, ProgramError> {
+        let prepared_inputs = self.prepare_inputs(public_inputs)?;
+
+        // e(-A, B) * e(alpha, beta) * e(prepared_inputs, gamma) * e(C, delta) == 1
+        pairing(&[
+            PairingElement {
+                g1: negate_g1(&proof.a)?,
+                g2: proof.b,
+            },
+            PairingElement {
+                g1: *self.alpha_g1,
+                g2: *self.beta_g2,
+            },
+            PairingElement {
+                g1: prepared_inputs,
+                g2: *self.gamma_g2,
+            },
+            PairingElement {
+                g1: proof.c,
+                g2: *self.delta_g2,
+            },
+        ])
+    }
+}
+
+/// Returns the negation of a G1 point.
+///
+/// Returns [`ProgramError::InvalidArgument`] if any coordinate is not smaller than
+/// the base field modulus.
+pub fn negate_g1(point: &G1Point) -> Result<G1Point, ProgramError> {
+    let (x, y) = point.0.split_at(FIELD_SIZE);
+    // SAFETY: Both halves are `FIELD_SIZE` bytes long.
+    let (x, y) = unsafe {
+        (
+            &*(x.as_ptr() as *const [u8; FIELD_SIZE]),
+            &*(y.as_ptr() as *const [u8; FIELD_SIZE]),
+        )
+    };
+
+    if !is_less_than(x, &BASE_FIELD_MODULUS) || !is_less_than(y, &BASE_FIELD_MODULUS) {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut negated = *point;
+
+    // the point at infinity is its own negation
+    if point.0.iter().any(|byte| *byte != 0) {
+        let mut borrow = 0;
+        for i in (0..FIELD_SIZE).rev() {
+            let (difference, overflow_1) = BASE_FIELD_MODULUS[i].overflowing_sub(y[i]);
+            let (difference, overflow_2) = difference.overflowing_sub(borrow);
+            negated.0[FIELD_SIZE + i] = difference;
+            borrow = (overflow_1 || overflow_2) as u8;
+        }
+    }
+
+    Ok(negated)
+}
+
+/// Checks whether the big-endian integer `value` is smaller than `bound`.
+#[inline]
+fn is_less_than(value: &[u8; FIELD_SIZE], bound: &[u8; FIELD_SIZE]) -> bool {
+    for (value, bound) in value.iter().zip(bound.iter()) {
+        if value != bound {
+            return value < bound;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifying key of a circuit proving the knowledge of `x` and `y` such that
+    /// `x * y` and `x + y` are the public inputs.
+    #[cfg(feature = "std")]
+    const VERIFYING_KEY: [u8; 640] = [
+        0x2b, 0x5a, 0xf8, 0xd0, 0xfd, 0xb9, 0x75, 0x98, 0x39, 0x73, 0xe3, 0xd4, 0xde, 0x8c, 0xa5,
+        0x98, 0x87, 0x2b, 0x0b, 0x39, 0x35, 0x49, 0x33, 0x48, 0x3a, 0x9f, 0x68, 0x1d, 0xed, 0xe1,
+        0x96, 0x26, 0x26, 0x36, 0xcd, 0x2d, 0x7d, 0x9a, 0x42, 0x7c, 0x81, 0x62, 0xd3, 0x2c, 0xe4,
+        0xd6, 0x1f, 0x84, 0x15, 0x5f, 0xc5, 0x01, 0x11, 0x6a, 0xa8, 0xa0, 0x54, 0xe8, 0x96, 0x59,
+        0x52, 0x40, 0xcd, 0x63, 0x2b, 0xb2, 0x3f, 0x2b, 0xd9, 0xad, 0xbe, 0xf3, 0xa1, 0x82, 0xf4,
+        0xce, 0x6f, 0xe6, 0xc7, 0x85, 0x40, 0x11, 0xb7, 0x2f, 0x80, 0x1f, 0xe0, 0xd0, 0x9b, 0x0d,
+        0xc2, 0xd5, 0xbf, 0xf8, 0xe8, 0xe1, 0x2f, 0xcf, 0x20, 0x72, 0xb5, 0x7d, 0x35, 0x8f, 0xd9,
+        0xe4, 0xeb, 0x27, 0x10, 0x09, 0xdb, 0x85, 0xc8, 0x54, 0x06, 0x84, 0x1d, 0x14, 0x31, 0x61,
+        0xf4, 0x64, 0x13, 0x1b, 0xc6, 0x89, 0x48, 0x5a, 0x1b, 0x48, 0x82, 0xc1, 0x96, 0x0f, 0xa7,
+        0x53, 0x44, 0x15, 0x3e, 0x7b, 0x0e, 0x03, 0x3f, 0xc2, 0xe4, 0x42, 0x97, 0xef, 0x0d, 0xe2,
+        0xd4, 0xb8, 0x67, 0x61, 0x23, 0x76, 0x69, 0x27, 0xa9, 0x79, 0x19, 0x5d, 0x9d, 0x82, 0x57,
+        0xc8, 0x5a, 0x64, 0x50, 0xea, 0xc7, 0x3f, 0x07, 0xa2, 0xb7, 0x52, 0x30, 0x53, 0xae, 0xfe,
+        0x4e, 0x50, 0xc7, 0x44, 0xdb, 0x13, 0xb7, 0x5a, 0x72, 0xe8, 0xcd, 0x68, 0x17, 0x7f, 0x57,
+        0x21, 0x38, 0x8c, 0x98, 0x84, 0x2e, 0x2d, 0x23, 0x28, 0x0c, 0xa5, 0xfb, 0x69, 0xeb, 0x08,
+        0x57, 0x3d, 0x02, 0xaf, 0xc0, 0x36, 0xb7, 0xaf, 0x41, 0x84, 0xf3, 0xea, 0xaa, 0x2c, 0x15,
+        0x12, 0xd0, 0x34, 0xe4, 0xad, 0x24, 0x3e, 0x34, 0xac, 0x94, 0x73, 0xd7, 0xd2, 0xb8, 0xda,
+        0xa6, 0x1d, 0xc1, 0x86, 0x48, 0x3b, 0xc1, 0xd5, 0xfc, 0x5a, 0xaf, 0xa6, 0xb8, 0x3a, 0x83,
+        0xb1, 0x24, 0xe4, 0x5d, 0xdf, 0x70, 0x27, 0x40, 0x73, 0x95, 0x08, 0x37, 0xed, 0x3b, 0x2c,
+        0xae, 0xe7, 0xd8, 0x79, 0x0a, 0x9c, 0xfb, 0x6a, 0x5d, 0xa6, 0x0e, 0xad, 0x54, 0xe5, 0x43,
+        0x8e, 0x95, 0x4e, 0x28, 0x6a, 0x68, 0x60, 0xf3, 0x35, 0x11, 0x44, 0x87, 0x6a, 0x87, 0x19,
+        0xcb, 0x09, 0xe3, 0xba, 0x93, 0x06, 0x7d, 0x4f, 0x78, 0x5f, 0xe0, 0x1e, 0x93, 0x50, 0xd6,
+        0xcf, 0xc1, 0x96, 0x8a, 0xf0, 0x10, 0x5c, 0xb2, 0xce, 0x57, 0xa1, 0x9d, 0x66, 0xce, 0x4c,
+        0xa0, 0x83, 0x1c, 0x5b, 0xe4, 0x84, 0xbe, 0x6d, 0x78, 0x49, 0x79, 0x34, 0xaf, 0xe0, 0x81,
+        0x18, 0x95, 0x3a, 0xaf, 0x9a, 0x0a, 0xaf, 0x13, 0x21, 0xbb, 0xf8, 0xfb, 0xe2, 0x31, 0x1d,
+        0xe3, 0x8f, 0x56, 0xd3, 0x06, 0x37, 0xc7, 0x20, 0x3a, 0x62, 0x61, 0xc9, 0x35, 0x64, 0xab,
+        0xcc, 0x2f, 0x65, 0x3e, 0xbe, 0x67, 0x14, 0x6e, 0x46, 0x08, 0xe1, 0xe1, 0x3e, 0x0f, 0xb7,
+        0x1f, 0x6d, 0xba, 0xc1, 0x1e, 0xc1, 0xc4, 0x73, 0x5d, 0x51, 0x83, 0x51, 0x32, 0x4f, 0x30,
+        0xb0, 0x46, 0x4f, 0xb1, 0xae, 0x29, 0xf9, 0x5c, 0x2c, 0x5f, 0xa7, 0x0e, 0x92, 0x84, 0x57,
+        0xed, 0xf5, 0xbc, 0xbe, 0x34, 0x72, 0x70, 0xa3, 0x35, 0xc8, 0x26, 0x8d, 0x74, 0x4a, 0x0a,
+        0xd3, 0xda, 0x55, 0x19, 0x29, 0x89, 0x61, 0xe1, 0xef, 0x98, 0xfe, 0x85, 0x6f, 0x00, 0xec,
+        0xb9, 0xba, 0x35, 0xf2, 0xd2, 0x5e, 0x8e, 0xbf, 0x51, 0xea, 0x0f, 0x3c, 0xf5, 0x7d, 0x5f,
+        0xb0, 0xa2, 0x55, 0x81, 0x8d, 0x81, 0xd9, 0x07, 0x41, 0x3e, 0x93, 0xe2, 0x71, 0xc9, 0xcb,
+        0x21, 0x87, 0x22, 0xa1, 0x20, 0x28, 0x61, 0x2d, 0xa0, 0xfe, 0xe1, 0x38, 0x0e, 0x70, 0x9d,
+        0x91, 0xea, 0x8b, 0x19, 0x6b, 0xca, 0x06, 0xe9, 0x6b, 0xa4, 0xdf, 0xf4, 0x36, 0xff, 0x25,
+        0x54, 0x9a, 0x30, 0x3c, 0xb6, 0x8d, 0xc3, 0x10, 0xc9, 0xc0, 0xee, 0x22, 0x8d, 0xf0, 0x14,
+        0xda, 0x56, 0xd8, 0x7d, 0x75, 0x93, 0xa0, 0xcc, 0x84, 0x25, 0xcf, 0x71, 0x08, 0xbb, 0xb6,
+        0x52, 0x1e, 0x3f, 0x00, 0x1d, 0xd5, 0x24, 0x63, 0xd5, 0x1a, 0x33, 0xaa, 0x7d, 0xda, 0xa2,
+        0x92, 0x48, 0x1b, 0xf8, 0x0e, 0xde, 0x21, 0x0a, 0xd3, 0x12, 0x2e, 0xdc, 0x62, 0x1c, 0x7d,
+        0xfe, 0x55, 0xd8, 0xe7, 0x58, 0xe9, 0x0e, 0x1f, 0x26, 0x17, 0x74, 0x49, 0x58, 0xd9, 0xb0,
+        0x8b, 0x20, 0x5c, 0xea, 0x58, 0xb3, 0x77, 0x08, 0x18, 0xbf, 0x9e, 0x3e, 0xb2, 0xa4, 0x6d,
+        0x30, 0x47, 0x92, 0xb4, 0x43, 0x20, 0xb4, 0x65, 0x16, 0xe6, 0xbe, 0x52, 0xd4, 0xd9, 0xae,
+        0x53, 0x2b, 0xa3, 0x6f, 0x99, 0x43, 0x1d, 0x89, 0x4e, 0xc0, 0x14, 0xa8, 0x05, 0xdf, 0x7c,
+        0xef, 0x24, 0x3f, 0x17, 0x3f, 0xbd, 0xae, 0x4d, 0xa4, 0xb3,
+    ];
+    /// Proof for `x = 3` and `y = 11`.
+    #[cfg(feature = "std")]
+    const PROOF: [u8; 256] = [
+        0x21, 0xd4, 0xee, 0x43, 0x56, 0x98, 0x86, 0x2e, 0x80, 0x07, 0xe6, 0x02, 0x27, 0x33, 0xf8,
+        0x79, 0x8b, 0x59, 0x46, 0xf2, 0x08, 0x4b, 0xda, 0x5d, 0x27, 0xc7, 0xfa, 0x09, 0x97, 0x5b,
+        0xb1, 0x99, 0x0c, 0x5f, 0x26, 0x40, 0xf5, 0xba, 0x2a, 0x45, 0x0b, 0x55, 0x79, 0x04, 0x58,
+        0x05, 0xe6, 0x2d, 0x55, 0x11, 0xd5, 0x9e, 0x90, 0x3d, 0x13, 0x47, 0x0a, 0xc9, 0x2f, 0xda,
+        0x5c, 0x2b, 0x52, 0x66, 0x2a, 0xdf, 0x16, 0x67, 0xa6, 0x4d, 0xc8, 0x06, 0x2e, 0xbd, 0x53,
+        0x44, 0x63, 0x65, 0xec, 0x9d, 0x85, 0xed, 0xe7, 0xd9, 0x89, 0x95, 0x91, 0x76, 0xf0, 0x4a,
+        0x69, 0xe6, 0xac, 0xd6, 0xc5, 0xb7, 0x1e, 0xd6, 0x04, 0x5e, 0x3c, 0x5f, 0x2f, 0x20, 0xbc,
+        0x01, 0x94, 0xc4, 0x1a, 0x86, 0xaf, 0xe3, 0xac, 0x32, 0x18, 0x5e, 0xdd, 0x93, 0x85, 0x4c,
+        0x87, 0x53, 0xdd, 0xc8, 0x74, 0x7f, 0xbe, 0xd2, 0x15, 0xb8, 0x47, 0x94, 0x57, 0x9a, 0xbe,
+        0x6d, 0xd3, 0xb3, 0x69, 0x8a, 0xd9, 0xd2, 0x83, 0x55, 0x74, 0xa0, 0x02, 0x30, 0xc8, 0x8c,
+        0x28, 0x60, 0x2a, 0xa2, 0xab, 0xb1, 0x47, 0xfe, 0x1f, 0xe6, 0x2e, 0xdc, 0xa7, 0xb1, 0x32,
+        0x7a, 0x38, 0x5e, 0xe4, 0xbc, 0x21, 0x0c, 0x37, 0x07, 0x9f, 0x46, 0x57, 0x14, 0xef, 0xff,
+        0x03, 0xb4, 0x48, 0x6e, 0x83, 0x3a, 0x1e, 0x50, 0xe5, 0xad, 0xd5, 0x52, 0x02, 0x87, 0xce,
+        0x58, 0xb1, 0xb9, 0x1a, 0x0c, 0x56, 0xa0, 0xce, 0x82, 0xb3, 0x06, 0xbf, 0xc7, 0x6f, 0x04,
+        0x6f, 0x4b, 0x0d, 0x74, 0x17, 0x51, 0xee, 0xa8, 0x0e, 0x04, 0x68, 0x83, 0xbf, 0xff, 0x2b,
+        0xb6, 0x04, 0xcb, 0xcd, 0xcb, 0xa6, 0xba, 0x7c, 0x5d, 0x83, 0x55, 0x1d, 0x72, 0x66, 0xdd,
+        0xf3, 0xec, 0x81, 0x26, 0xd1, 0x59, 0x0f, 0x85, 0xc5, 0x57, 0x22, 0x91, 0x98, 0xda, 0xb7,
+        0x40,
+    ];
+
+    /// A backend implementing the `alt_bn128` group operations with arkworks.
+    #[cfg(feature = "std")]
+    mod bn254 {
+        extern crate std;
+
+        use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+        use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+        use ark_ff::{BigInteger, One, PrimeField};
+        use std::vec::Vec;
+
+        use crate::{
+            alt_bn128::{
+                ADD, ADDITION_INPUT_LEN, FIELD_SIZE, G1_POINT_SIZE, MUL, MULTIPLICATION_INPUT_LEN,
+                PAIRING, PAIRING_ELEMENT_LEN, PAIRING_OUTPUT_LEN,
+            },
+            host::Backend,
+        };
+
+        pub struct Bn254Backend;
+
+        impl Backend for Bn254Backend {
+            fn alt_bn128_group_op(&self, op: u64, input: &[u8]) -> Option<Vec<u8>> {
+                match op {
+                    ADD => {
+                        let input = padded(input, ADDITION_INPUT_LEN)?;
+                        let p1 = g1(&input[..G1_POINT_SIZE])?;
+                        let p2 = g1(&input[G1_POINT_SIZE..])?;
+                        Some(encode_g1(&(p1 + p2).into_affine()))
+                    }
+                    MUL => {
+                        let input = padded(input, MULTIPLICATION_INPUT_LEN)?;
+                        let point = g1(&input[..G1_POINT_SIZE])?;
+                        let scalar = Fr::from_be_bytes_mod_order(&input[G1_POINT_SIZE..]);
+                        Some(encode_g1(&(point * scalar).into_affine()))
+                    }
+                    PAIRING => {
+                        if input.len() % PAIRING_ELEMENT_LEN != 0 {
+                            return None;
+                        }
+                        let mut g1s = Vec::new();
+                        let mut g2s = Vec::new();
+                        for element in input.chunks(PAIRING_ELEMENT_LEN) {
+                            g1s.push(g1(&element[..G1_POINT_SIZE])?);
+                            g2s.push(g2(&element[G1_POINT_SIZE..])?);
+                        }
+                        let mut output = std::vec![0; PAIRING_OUTPUT_LEN];
+                        output[PAIRING_OUTPUT_LEN - 1] =
+                            Bn254::multi_pairing(g1s, g2s).0.is_one() as u8;
+                        Some(output)
+                    }
+                    _ => None,
+                }
+            }
+        }
+
+        fn padded(input: &[u8], len: usize) -> Option<Vec<u8>> {
+            if input.len() > len {
+                return None;
+            }
+            let mut padded = input.to_vec();
+            padded.resize(len, 0);
+            Some(padded)
+        }
+
+        fn fq(bytes: &[u8]) -> Option<Fq> {
+            let value = Fq::from_be_bytes_mod_order(bytes);
+            (value.into_bigint().to_bytes_be() == bytes).then_some(value)
+        }
+
+        fn g1(bytes: &[u8]) -> Option<G1Affine> {
+            if bytes.iter().all(|byte| *byte == 0) {
+                return Some(G1Affine::zero());
+            }
+            let point =
+                G1Affine::new_unchecked(fq(&bytes[..FIELD_SIZE])?, fq(&bytes[FIELD_SIZE..])?);
+            point.is_on_curve().then_some(point)
+        }
+
+        fn g2(bytes: &[u8]) -> Option<G2Affine> {
+            if bytes.iter().all(|byte| *byte == 0) {
+                return Some(G2Affine::zero());
+            }
+            let field = |offset: usize| fq(&bytes[offset..offset + FIELD_SIZE]);
+            let point = G2Affine::new_unchecked(
+                Fq2::new(field(FIELD_SIZE)?, field(0)?),
+                Fq2::new(field(FIELD_SIZE * 3)?, field(FIELD_SIZE * 2)?),
+            );
+            (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+                .then_some(point)
+        }
+
+        fn encode_g1(point: &G1Affine) -> Vec<u8> {
+            match point.xy() {
+                Some((x, y)) => {
+                    [x.into_bigint().to_bytes_be(), y.into_bigint().to_bytes_be()].concat()
+                }
+                None => std::vec![0; G1_POINT_SIZE],
+            }
+        }
+    }
+
+    #[test]
+    fn test_negate_g1() {
+        // generator (1, 2)
+        let mut generator = G1Point::IDENTITY;
+        generator.0[FIELD_SIZE - 1] = 1;
+        generator.0[G1_POINT_SIZE - 1] = 2;
+
+        let negated = negate_g1(&generator).unwrap();
+        assert_eq!(negated.0[..FIELD_SIZE], generator.0[..FIELD_SIZE]);
+
+        let mut y = BASE_FIELD_MODULUS;
+        y[FIELD_SIZE - 1] -= 2;
+        assert_eq!(negated.0[FIELD_SIZE..], y);
+
+        assert_eq!(negate_g1(&negated).unwrap(), generator);
+        assert_eq!(negate_g1(&G1Point::IDENTITY).unwrap(), G1Point::IDENTITY);
+
+        let mut invalid = generator;
+        invalid.0[FIELD_SIZE..].copy_from_slice(&BASE_FIELD_MODULUS);
+        assert_eq!(negate_g1(&invalid), Err(ProgramError::InvalidArgument));
+    }
+
+    #[test]
+    fn test_verifying_key() {
+        let mut bytes = [0u8; VerifyingKey::HEADER_LEN + G1_POINT_SIZE * 3];
+        bytes[0] = 1;
+        bytes[VerifyingKey::HEADER_LEN] = 2;
+
+        let verifying_key = VerifyingKey::from_bytes(&bytes).unwrap();
+        assert_eq!(verifying_key.alpha_g1.0[0], 1);
+        assert_eq!(verifying_key.ic[0].0[0], 2);
+        assert_eq!(verifying_key.public_inputs_len(), 2);
+
+        assert!(VerifyingKey::from_bytes(&bytes[..VerifyingKey::HEADER_LEN]).is_err());
+        assert!(VerifyingKey::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // wrong number of public inputs
+        assert_eq!(
+            verifying_key.prepare_inputs(&[[0; FIELD_SIZE]]),
+            Err(ProgramError::InvalidArgument)
+        );
+        // public input not in the scalar field
+        assert_eq!(
+            verifying_key.prepare_inputs(&[[0; FIELD_SIZE], SCALAR_FIELD_MODULUS]),
+            Err(ProgramError::InvalidArgument)
+        );
+        // zero inputs do not require any curve operation
+        assert_eq!(
+            verifying_key.prepare_inputs(&[[0; FIELD_SIZE]; 2]),
+            Ok(verifying_key.ic[0])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_verify() {
+        extern crate std;
+
+        let _guard = crate::host::set_backend(std::rc::Rc::new(bn254::Bn254Backend));
+
+        let verifying_key = VerifyingKey::from_bytes(&VERIFYING_KEY).unwrap();
+        let proof = Proof::from_bytes(&PROOF).unwrap();
+
+        let mut product = [0; FIELD_SIZE];
+        product[FIELD_SIZE - 1] = 33;
+        let mut sum = [0; FIELD_SIZE];
+        sum[FIELD_SIZE - 1] = 14;
+
+        assert_eq!(verifying_key.public_inputs_len(), 2);
+        assert_eq!(verifying_key.verify(proof, &[product, sum]), Ok(true));
+
+        // wrong public inputs
+        assert_eq!(verifying_key.verify(proof, &[sum, product]), Ok(false));
+
+        // tampered proof, with the `A` and `C` points swapped
+        let mut tampered = *proof;
+        tampered.a = proof.c;
+        tampered.c = proof.a;
+        assert_eq!(verifying_key.verify(&tampered, &[product, sum]), Ok(false));
+
+        // a point not on the curve
+        let mut tampered = *proof;
+        tampered.a.0[G1_POINT_SIZE - 1] ^= 1;
+        assert_eq!(
+            verifying_key.verify(&tampered, &[product, sum]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
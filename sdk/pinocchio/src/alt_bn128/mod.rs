@@ -0,0 +1,266 @@
+//! Operations on the `alt_bn128` (BN254) elliptic curve.
+//!
+//! Provides typed wrappers over the `sol_alt_bn128_group_op` syscall, following the
+//! encoding of [EIP-196] and [EIP-197]: field elements and scalars are 32-byte
+//! big-endian integers, G1 points are encoded as `x || y` and G2 points as
+//! `x_im || x_re || y_im || y_re`. The point at infinity is encoded as all zeros.
+//!
+//! The `alt_bn128_*` functions take the raw syscall input and validate its length
+//! the same way as the runtime, while [`add`], [`multiply`] and [`pairing`] operate
+//! on typed points. Point compression is available in the [`compression`] module and
+//! Groth16 proof verification in the [`groth16`] module.
+//!
+//! The curve operations are only available on-chain. When not compiled for the
+//! `solana` target, all operations fail, unless the group operations are
+//! implemented by the host backend of a native test.
+//!
+//! [EIP-196]: https://eips.ethereum.org/EIPS/eip-196
+//! [EIP-197]: https://eips.ethereum.org/EIPS/eip-197
+
+use crate::program_error::ProgramError;
+
+pub mod compression;
+pub mod groth16;
+
+/// Size of a field element or scalar in bytes.
+pub const FIELD_SIZE: usize = 32;
+
+/// Size of an encoded G1 point in bytes.
+pub const G1_POINT_SIZE: usize = FIELD_SIZE * 2;
+
+/// Size of an encoded G2 point in bytes.
+pub const G2_POINT_SIZE: usize = FIELD_SIZE * 4;
+
+/// Maximum input length of the addition operation.
+pub const ADDITION_INPUT_LEN: usize = G1_POINT_SIZE * 2;
+
+/// Maximum input length of the multiplication operation.
+pub const MULTIPLICATION_INPUT_LEN: usize = G1_POINT_SIZE + FIELD_SIZE;
+
+/// Length of each element of the pairing input.
+pub const PAIRING_ELEMENT_LEN: usize = G1_POINT_SIZE + G2_POINT_SIZE;
+
+/// Output length of the addition operation.
+pub const ADDITION_OUTPUT_LEN: usize = G1_POINT_SIZE;
+
+/// Output length of the multiplication operation.
+pub const MULTIPLICATION_OUTPUT_LEN: usize = G1_POINT_SIZE;
+
+/// Output length of the pairing operation.
+pub const PAIRING_OUTPUT_LEN: usize = 32;
+
+/// Identifier of the addition operation.
+pub const ADD: u64 = 0;
+
+/// Identifier of the multiplication operation.
+///
+/// The identifier `1` is reserved for a subtraction operation, which is not
+/// supported by the runtime.
+pub const MUL: u64 = 2;
+
+/// Identifier of the pairing operation.
+pub const PAIRING: u64 = 3;
+
+/// A point of the G1 group.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct G1Point(pub [u8; G1_POINT_SIZE]);
+
+impl G1Point {
+    /// The point at infinity.
+    pub const IDENTITY: Self = Self([0; G1_POINT_SIZE]);
+}
+
+impl From<[u8; G1_POINT_SIZE]> for G1Point {
+    #[inline(always)]
+    fn from(bytes: [u8; G1_POINT_SIZE]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// A point of the G2 group.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct G2Point(pub [u8; G2_POINT_SIZE]);
+
+impl G2Point {
+    /// The point at infinity.
+    pub const IDENTITY: Self = Self([0; G2_POINT_SIZE]);
+}
+
+impl From<[u8; G2_POINT_SIZE]> for G2Point {
+    #[inline(always)]
+    fn from(bytes: [u8; G2_POINT_SIZE]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// A pair of points used as input of the [`pairing`] check.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PairingElement {
+    /// Point of the G1 group.
+    pub g1: G1Point,
+
+    /// Point of the G2 group.
+    pub g2: G2Point,
+}
+
+/// Performs the group operation `op` on `input`, writing the result to `result`.
+#[inline]
+fn group_op(op: u64, input: &[u8], result: &mut [u8]) -> Result<(), ProgramError> {
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        crate::syscalls::sol_alt_bn128_group_op(
+            op,
+            input.as_ptr(),
+            input.len() as u64,
+            result.as_mut_ptr(),
+        )
+    };
+
+    #[cfg(not(target_os = "solana"))]
+    let result = unsafe {
+        crate::host::sol_alt_bn128_group_op(
+            op,
+            input.as_ptr(),
+            input.len() as u64,
+            result.as_mut_ptr(),
+        )
+    };
+
+    match result {
+        crate::SUCCESS => Ok(()),
+        _ => Err(ProgramError::InvalidArgument),
+    }
+}
+
+/// Adds two G1 points, given as `p1 || p2`.
+///
+/// Inputs shorter than [`ADDITION_INPUT_LEN`] are padded with zeros by the runtime.
+///
+/// Returns [`ProgramError::InvalidInstructionData`] if the input is longer than
+/// [`ADDITION_INPUT_LEN`], and [`ProgramError::InvalidArgument`] if any of the
+/// points is invalid.
+#[inline]
+pub fn alt_bn128_addition(input: &[u8]) -> Result<[u8; ADDITION_OUTPUT_LEN], ProgramError> {
+    if input.len() > ADDITION_INPUT_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut result = [0u8; ADDITION_OUTPUT_LEN];
+    group_op(ADD, input, &mut result)?;
+    Ok(result)
+}
+
+/// Multiplies a G1 point by a scalar, given as `p || s`.
+///
+/// Inputs shorter than [`MULTIPLICATION_INPUT_LEN`] are padded with zeros by the
+/// runtime.
+///
+/// Returns [`ProgramError::InvalidInstructionData`] if the input is longer than
+/// [`MULTIPLICATION_INPUT_LEN`], and [`ProgramError::InvalidArgument`] if the point
+/// is invalid.
+#[inline]
+pub fn alt_bn128_multiplication(
+    input: &[u8],
+) -> Result<[u8; MULTIPLICATION_OUTPUT_LEN], ProgramError> {
+    if input.len() > MULTIPLICATION_INPUT_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut result = [0u8; MULTIPLICATION_OUTPUT_LEN];
+    group_op(MUL, input, &mut result)?;
+    Ok(result)
+}
+
+/// Performs the pairing check on a list of `(G1, G2)` point pairs.
+///
+/// The result is `1` encoded as a 32-byte big-endian integer if the product of the
+/// pairings is the identity, and `0` otherwise.
+///
+/// Returns [`ProgramError::InvalidInstructionData`] if the input length is not a
+/// multiple of [`PAIRING_ELEMENT_LEN`], and [`ProgramError::InvalidArgument`] if
+/// any of the points is invalid.
+#[inline]
+pub fn alt_bn128_pairing(input: &[u8]) -> Result<[u8; PAIRING_OUTPUT_LEN], ProgramError> {
+    let remainder = input.len() % PAIRING_ELEMENT_LEN;
+    if remainder != 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut result = [0u8; PAIRING_OUTPUT_LEN];
+    group_op(PAIRING, input, &mut result)?;
+    Ok(result)
+}
+
+/// Adds two G1 points.
+///
+/// Returns [`ProgramError::InvalidArgument`] if any of the points is invalid.
+#[inline]
+pub fn add(left: &G1Point, right: &G1Point) -> Result<G1Point, ProgramError> {
+    let mut input = [0u8; ADDITION_INPUT_LEN];
+    input[..G1_POINT_SIZE].copy_from_slice(&left.0);
+    input[G1_POINT_SIZE..].copy_from_slice(&right.0);
+
+    let mut result = G1Point::IDENTITY;
+    group_op(ADD, &input, &mut result.0)?;
+    Ok(result)
+}
+
+/// Multiplies a G1 point by a 32-byte big-endian scalar.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the point is invalid.
+#[inline]
+pub fn multiply(point: &G1Point, scalar: &[u8; FIELD_SIZE]) -> Result<G1Point, ProgramError> {
+    let mut input = [0u8; MULTIPLICATION_INPUT_LEN];
+    input[..G1_POINT_SIZE].copy_from_slice(&point.0);
+    input[G1_POINT_SIZE..].copy_from_slice(scalar);
+
+    let mut result = G1Point::IDENTITY;
+    group_op(MUL, &input, &mut result.0)?;
+    Ok(result)
+}
+
+/// Checks whether the product of the pairings of each element is the identity.
+///
+/// Returns [`ProgramError::InvalidArgument`] if any of the points is invalid.
+#[inline]
+pub fn pairing(elements: &[PairingElement]) -> Result<bool, ProgramError> {
+    // SAFETY: `PairingElement` has no padding, so the elements are contiguous
+    // `PAIRING_ELEMENT_LEN` byte sequences.
+    let input = unsafe {
+        core::slice::from_raw_parts(
+            elements.as_ptr() as *const u8,
+            elements.len() * PAIRING_ELEMENT_LEN,
+        )
+    };
+
+    let mut result = [0u8; PAIRING_OUTPUT_LEN];
+    group_op(PAIRING, input, &mut result)?;
+
+    let (value, one) = result.split_at(PAIRING_OUTPUT_LEN - 1);
+    Ok(value.iter().all(|byte| *byte == 0) && one[0] == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_length() {
+        assert_eq!(core::mem::size_of::<PairingElement>(), PAIRING_ELEMENT_LEN);
+        assert_eq!(
+            alt_bn128_addition(&[0; ADDITION_INPUT_LEN + 1]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            alt_bn128_multiplication(&[0; MULTIPLICATION_INPUT_LEN + 1]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            alt_bn128_pairing(&[0; PAIRING_ELEMENT_LEN + 1]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Modular exponentiation of big integers.
+//!
+//! Provides a safe wrapper over the `sol_big_mod_exp` syscall, which computes
+//! `base ^ exponent mod modulus` for big-endian integers of up to [`MAX_LEN`]
+//! bytes. The [`rsa`] module builds on it to verify RSA signatures.
+
+use crate::program_error::ProgramError;
+
+pub mod rsa;
+
+/// Maximum length in bytes of each input of the modular exponentiation.
+pub const MAX_LEN: usize = 512;
+
+/// Parameters of the `sol_big_mod_exp` syscall.
+///
+/// All integers are big-endian and the result has the same length as the modulus.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct BigModExpParams {
+    /// Address of the base.
+    pub base: *const u8,
+
+    /// Length of the base in bytes.
+    pub base_len: u64,
+
+    /// Address of the exponent.
+    pub exponent: *const u8,
+
+    /// Length of the exponent in bytes.
+    pub exponent_len: u64,
+
+    /// Address of the modulus.
+    pub modulus: *const u8,
+
+    /// Length of the modulus in bytes.
+    pub modulus_len: u64,
+}
+
+/// Computes `base ^ exponent mod modulus`, writing the result to `result`.
+///
+/// All values are big-endian integers and `result` must have the same length as
+/// `modulus`. The result is zero if the modulus is zero or one.
+///
+/// Returns [`ProgramError::InvalidArgument`] if any input is longer than [`MAX_LEN`]
+/// bytes or the length of `result` is not the length of `modulus`.
+#[inline]
+pub fn big_mod_exp(
+    base: &[u8],
+    exponent: &[u8],
+    modulus: &[u8],
+    result: &mut [u8],
+) -> Result<(), ProgramError> {
+    if base.len() > MAX_LEN
+        || exponent.len() > MAX_LEN
+        || modulus.len() > MAX_LEN
+        || result.len() != modulus.len()
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let params = BigModExpParams {
+        base: base.as_ptr(),
+        base_len: base.len() as u64,
+        exponent: exponent.as_ptr(),
+        exponent_len: exponent.len() as u64,
+        modulus: modulus.as_ptr(),
+        modulus_len: modulus.len() as u64,
+    };
+
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        crate::syscalls::sol_big_mod_exp(&params as *const _ as *const u8, result.as_mut_ptr())
+    };
+
+    #[cfg(not(target_os = "solana"))]
+    let result = unsafe {
+        crate::host::sol_big_mod_exp(&params as *const _ as *const u8, result.as_mut_ptr())
+    };
+
+    match result {
+        crate::SUCCESS => Ok(()),
+        _ => Err(ProgramError::InvalidArgument),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_big_mod_exp() {
+        let mut result = [0u8; 2];
+        big_mod_exp(
+            &[0x12, 0x34, 0x56],
+            &[0x01, 0x01],
+            &[0x10, 0x03],
+            &mut result,
+        )
+        .unwrap();
+        assert_eq!(result, [0x0d, 0xc3]);
+
+        assert_eq!(
+            big_mod_exp(&[2], &[3], &[5], &mut result),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            big_mod_exp(&[0; MAX_LEN + 1], &[3], &[5], &mut result[..1]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! RSA signature verification.
+//!
+//! Supports the RSASSA-PKCS1-v1_5 and RSASSA-PSS signature schemes of [RFC 8017]
+//! with SHA-256, for keys of up to [`MAX_LEN`] bytes (4096 bits). Signatures are
+//! verified against the SHA-256 hash of the message, which can be computed with
+//! [`hash::sha256`](crate::hash::sha256).
+//!
+//! The public key operation uses the `sol_big_mod_exp` syscall; its cost grows with
+//! the size of the modulus and the exponent, so keys with the common public exponent
+//! `65537` are the cheapest to verify.
+//!
+//! [RFC 8017]: https://www.rfc-editor.org/rfc/rfc8017
+//!
+//! # Example
+//!
+//! ```
+//! use pinocchio::{big_mod_exp::rsa::RsaPublicKey, hash::sha256};
+//! # let modulus = [0xffu8; 128];
+//! # let signature = [0u8; 128];
+//!
+//! let public_key = RsaPublicKey::new(&modulus, &[0x01, 0x00, 0x01]).unwrap();
+//! let message_hash = sha256(&[b"hello pinocchio"]);
+//!
+//! if !public_key.verify_pkcs1v15_sha256(&message_hash, &signature).unwrap() {
+//!     // invalid signature
+//! }
+//! ```
+
+use super::{big_mod_exp, MAX_LEN};
+use crate::{
+    hash::{sha256, HASH_BYTES},
+    program_error::ProgramError,
+};
+
+/// DER encoding of the `DigestInfo` prefix for SHA-256 hashes.
+const SHA256_DIGEST_INFO_PREFIX: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+/// An RSA public key.
+#[derive(Clone, Copy, Debug)]
+pub struct RsaPublicKey<'a> {
+    /// Modulus as a big-endian integer.
+    modulus: &'a [u8],
+
+    /// Public exponent as a big-endian integer.
+    exponent: &'a [u8],
+}
+
+impl<'a> RsaPublicKey<'a> {
+    /// Creates a public key from its big-endian modulus and exponent.
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if the modulus is empty, has a
+    /// leading zero byte or any value is longer than [`MAX_LEN`] bytes.
+    pub fn new(modulus: &'a [u8], exponent: &'a [u8]) -> Result<Self, ProgramError> {
+        match modulus.first() {
+            Some(byte) if *byte != 0 && modulus.len() <= MAX_LEN && exponent.len() <= MAX_LEN => {
+                Ok(Self { modulus, exponent })
+            }
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+
+    /// Returns the modulus.
+    #[inline(always)]
+    pub fn modulus(&self) -> &'a [u8] {
+        self.modulus
+    }
+
+    /// Returns the public exponent.
+    #[inline(always)]
+    pub fn exponent(&self) -> &'a [u8] {
+        self.exponent
+    }
+
+    /// Verifies an RSASSA-PKCS1-v1_5 signature of a message with SHA-256 hash
+    /// `message_hash`.
+    ///
+    /// Returns `Ok(false)` if the signature is not valid, and
+    /// [`ProgramError::InvalidArgument`] if the modulus is too short for the scheme.
+    pub fn verify_pkcs1v15_sha256(
+        &self,
+        message_hash: &[u8; HASH_BYTES],
+        signature: &[u8],
+    ) -> Result<bool, ProgramError> {
+        let digest_info_len = SHA256_DIGEST_INFO_PREFIX.len() + HASH_BYTES;
+
+        if self.modulus.len() < digest_info_len + 11 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut buffer = [0u8; MAX_LEN];
+        let encoded = match self.recover(signature, &mut buffer)? {
+            Some(encoded) => encoded,
+            None => return Ok(false),
+        };
+
+        // EM = 0x00 || 0x01 || PS || 0x00 || T, where PS is a sequence of 0xff bytes
+        let (padding, digest_info) = encoded.split_at(encoded.len() - digest_info_len);
+        let (prefix, hash) = digest_info.split_at(SHA256_DIGEST_INFO_PREFIX.len());
+
+        Ok(padding[0] == 0x00
+            && padding[1] == 0x01
+            && padding[2..padding.len() - 1]
+                .iter()
+                .all(|byte| *byte == 0xff)
+            && padding[padding.len() - 1] == 0x00
+            && prefix == SHA256_DIGEST_INFO_PREFIX
+            && hash == message_hash)
+    }
+
+    /// Verifies an RSASSA-PSS signature of a message with SHA-256 hash `message_hash`,
+    /// using SHA-256 as the mask generation hash function and a salt of `salt_len`
+    /// bytes.
+    ///
+    /// Returns `Ok(false)` if the signature is not valid.
+    pub fn verify_pss_sha256(
+        &self,
+        message_hash: &[u8; HASH_BYTES],
+        signature: &[u8],
+        salt_len: usize,
+    ) -> Result<bool, ProgramError> {
+        // the encoded message must hold the hash, the salt and two bytes of padding
+        let min_len = match salt_len.checked_add(HASH_BYTES + 2) {
+            Some(min_len) => min_len,
+            None => return Ok(false),
+        };
+
+        let mut buffer = [0u8; MAX_LEN];
+        let recovered = match self.recover(signature, &mut buffer)? {
+            Some(recovered) => recovered,
+            None => return Ok(false),
+        };
+
+        // the encoded message has one bit less than the modulus
+        let modulus_bits = self.modulus.len() * 8 - self.modulus[0].leading_zeros() as usize;
+        let encoded_bits = modulus_bits - 1;
+        let encoded_len = encoded_bits.div_ceil(8);
+
+        let (leading, encoded) = recovered.split_at_mut(recovered.len() - encoded_len);
+
+        if leading.iter().any(|byte| *byte != 0)
+            || encoded_len < min_len
+            || encoded[encoded_len - 1] != 0xbc
+        {
+            return Ok(false);
+        }
+
+        // EM = maskedDB || H || 0xbc
+        let (db, hash) = encoded.split_at_mut(encoded_len - HASH_BYTES - 1);
+        let hash = &hash[..HASH_BYTES];
+
+        let zero_bits = 8 * encoded_len - encoded_bits;
+        let mask = 0xffu8 >> zero_bits;
+
+        if db[0] & !mask != 0 {
+            return Ok(false);
+        }
+
+        // MGF1 with SHA-256
+        for (counter, chunk) in db.chunks_mut(HASH_BYTES).enumerate() {
+            let db_mask = sha256(&[hash, &(counter as u32).to_be_bytes()]);
+            for (byte, mask) in chunk.iter_mut().zip(db_mask.iter()) {
+                *byte ^= mask;
+            }
+        }
+        db[0] &= mask;
+
+        // DB = PS || 0x01 || salt, where PS is a sequence of zero bytes
+        let (padding, salt) = db.split_at(db.len() - salt_len);
+
+        if padding[..padding.len() - 1].iter().any(|byte| *byte != 0)
+            || padding[padding.len() - 1] != 0x01
+        {
+            return Ok(false);
+        }
+
+        // H' = Hash(0x00 * 8 || mHash || salt)
+        Ok(sha256(&[&[0; 8], message_hash, salt]) == hash)
+    }
+
+    /// Applies the public key operation to the signature, returning the recovered
+    /// encoded message or `None` if the signature is not an integer smaller than the
+    /// modulus with the same length.
+    fn recover<'b>(
+        &self,
+        signature: &[u8],
+        buffer: &'b mut [u8; MAX_LEN],
+    ) -> Result<Option<&'b mut [u8]>, ProgramError> {
+        // comparing big-endian integers of the same length is a lexicographic comparison
+        if signature.len() != self.modulus.len() || signature >= self.modulus {
+            return Ok(None);
+        }
+
+        let recovered = &mut buffer[..self.modulus.len()];
+        big_mod_exp(signature, self.exponent, self.modulus, recovered)?;
+
+        Ok(Some(recovered))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPONENT: [u8; 3] = [0x01, 0x00, 0x01];
+
+    const MODULUS: [u8; 128] = [
+        0xd0, 0xb6, 0x8c, 0xac, 0x19, 0x2d, 0xa6, 0x3f, 0xed, 0xfa, 0xfa, 0xf2, 0xf0, 0x66, 0xe9,
+        0x8d, 0xfe, 0xcc, 0xcf, 0x09, 0x86, 0x6a, 0x60, 0xb6, 0x5e, 0x40, 0xc1, 0xf3, 0xec, 0x49,
+        0x4a, 0x69, 0x3f, 0xea, 0xd9, 0x0f, 0x7c, 0x1c, 0x1e, 0x64, 0xca, 0xc0, 0x03, 0xff, 0x65,
+        0x8b, 0x0e, 0xec, 0x5b, 0x28, 0xf3, 0xfe, 0xbe, 0xc5, 0xcd, 0x15, 0xe5, 0xf9, 0x9a, 0x7e,
+        0x84, 0x46, 0xb7, 0xfb, 0xfd, 0x41, 0xcf, 0x91, 0xb9, 0x1a, 0xe0, 0x2d, 0xd2, 0x50, 0x8c,
+        0xf5, 0x69, 0x8e, 0xb1, 0x9d, 0x7b, 0x54, 0x74, 0xd3, 0xd4, 0x4c, 0x56, 0x3f, 0x55, 0x17,
+        0x6a, 0x19, 0x3a, 0xd7, 0x18, 0xe8, 0x40, 0xb1, 0xd2, 0xe1, 0x2b, 0xdc, 0x50, 0x1e, 0xa6,
+        0xc0, 0x28, 0xea, 0x90, 0xed, 0xc6, 0xbd, 0xe1, 0x34, 0x5c, 0x1a, 0x6e, 0xcc, 0x2b, 0xa6,
+        0x1d, 0x01, 0x4c, 0x23, 0x16, 0xac, 0xd1, 0xaf,
+    ];
+
+    const PKCS1V15_SIGNATURE: [u8; 128] = [
+        0x39, 0x5e, 0x23, 0x56, 0x1e, 0xff, 0xa2, 0x66, 0xc2, 0x03, 0x6c, 0xe9, 0xad, 0x50, 0x1a,
+        0x41, 0x9a, 0x03, 0x41, 0xbd, 0x7f, 0xad, 0x05, 0x40, 0x68, 0x62, 0x65, 0x68, 0x5d, 0x37,
+        0x98, 0x5e, 0xcf, 0x63, 0xd0, 0x6a, 0x1c, 0x4f, 0xec, 0xc3, 0x19, 0x6b, 0x9e, 0xa6, 0x11,
+        0x3a, 0x6e, 0x48, 0x0a, 0x69, 0x0d, 0x5a, 0x5b, 0x50, 0x99, 0x1c, 0x0d, 0x02, 0x58, 0xdb,
+        0x62, 0xcd, 0xff, 0x30, 0x3c, 0x5e, 0x1e, 0x18, 0x6f, 0x7a, 0xc7, 0x36, 0x4a, 0x58, 0x8d,
+        0x19, 0x19, 0xc8, 0x7a, 0x09, 0x4e, 0xac, 0x64, 0x4c, 0x52, 0x65, 0xe6, 0x28, 0xd5, 0xf8,
+        0xe2, 0x6a, 0x8f, 0x70, 0x13, 0xeb, 0x74, 0x7b, 0xde, 0x45, 0xf8, 0xe5, 0xdc, 0x76, 0x9b,
+        0x8c, 0xde, 0xf6, 0xb4, 0x92, 0xde, 0xa2, 0x18, 0x7d, 0xd4, 0x72, 0xde, 0x91, 0x3a, 0x51,
+        0xbe, 0x57, 0xc0, 0x3e, 0x7b, 0x86, 0x00, 0x7e,
+    ];
+
+    const PSS_SIGNATURE: [u8; 128] = [
+        0x89, 0x0e, 0xaa, 0x29, 0x79, 0x01, 0x72, 0x92, 0x55, 0x9f, 0x49, 0x96, 0x37, 0xba, 0x10,
+        0x80, 0x7e, 0x49, 0xcb, 0x90, 0xfb, 0x0a, 0x11, 0xfe, 0xd2, 0xce, 0x5e, 0x74, 0x31, 0xa3,
+        0x78, 0xb7, 0x34, 0x8f, 0x75, 0x13, 0x5c, 0x09, 0x5c, 0xcb, 0x85, 0x18, 0xed, 0xda, 0x94,
+        0x4b, 0xbb, 0xf3, 0xb5, 0xb5, 0x57, 0xe6, 0x18, 0xe7, 0x72, 0x7e, 0x47, 0xad, 0xff, 0x23,
+        0x3c, 0xe0, 0xfb, 0xfc, 0x77, 0x52, 0xd5, 0x97, 0xba, 0xb9, 0x93, 0x86, 0x2a, 0xab, 0x13,
+        0xbf, 0x6c, 0x7f, 0x00, 0x2b, 0xeb, 0x30, 0x9b, 0x7f, 0x9a, 0xff, 0xa0, 0x1a, 0xe4, 0x54,
+        0x6f, 0x95, 0x5e, 0x5e, 0x3a, 0x45, 0x36, 0x5d, 0x3a, 0x1d, 0x7f, 0x41, 0x40, 0x66, 0xff,
+        0x1a, 0x43, 0xab, 0x50, 0xc3, 0x3d, 0xc8, 0x6a, 0x5c, 0x80, 0xe7, 0x41, 0xae, 0x65, 0x82,
+        0xae, 0x23, 0x3e, 0xc8, 0x31, 0xd4, 0x37, 0xf5,
+    ];
+
+    #[test]
+    fn test_verify_pkcs1v15_sha256() {
+        let public_key = RsaPublicKey::new(&MODULUS, &EXPONENT).unwrap();
+        let message_hash = sha256(&[b"hello pinocchio"]);
+
+        assert!(public_key
+            .verify_pkcs1v15_sha256(&message_hash, &PKCS1V15_SIGNATURE)
+            .unwrap());
+
+        let other_hash = sha256(&[b"hello pinocchio!"]);
+        assert!(!public_key
+            .verify_pkcs1v15_sha256(&other_hash, &PKCS1V15_SIGNATURE)
+            .unwrap());
+
+        let mut signature = PKCS1V15_SIGNATURE;
+        signature[64] ^= 1;
+        assert!(!public_key
+            .verify_pkcs1v15_sha256(&message_hash, &signature)
+            .unwrap());
+        assert!(!public_key
+            .verify_pkcs1v15_sha256(&message_hash, &PKCS1V15_SIGNATURE[1..])
+            .unwrap());
+        assert!(!public_key
+            .verify_pkcs1v15_sha256(&message_hash, &MODULUS)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_verify_pss_sha256() {
+        let public_key = RsaPublicKey::new(&MODULUS, &EXPONENT).unwrap();
+        let message_hash = sha256(&[b"hello pinocchio"]);
+
+        assert!(public_key
+            .verify_pss_sha256(&message_hash, &PSS_SIGNATURE, 32)
+            .unwrap());
+        assert!(!public_key
+            .verify_pss_sha256(&message_hash, &PSS_SIGNATURE, 20)
+            .unwrap());
+        assert!(!public_key
+            .verify_pss_sha256(&message_hash, &PSS_SIGNATURE, 128)
+            .unwrap());
+        assert!(!public_key
+            .verify_pss_sha256(&message_hash, &PSS_SIGNATURE, usize::MAX)
+            .unwrap());
+
+        let other_hash = sha256(&[b"hello pinocchio!"]);
+        assert!(!public_key
+            .verify_pss_sha256(&other_hash, &PSS_SIGNATURE, 32)
+            .unwrap());
+
+        // a PKCS#1 v1.5 signature is not a valid PSS signature
+        assert!(!public_key
+            .verify_pss_sha256(&message_hash, &PKCS1V15_SIGNATURE, 32)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        assert!(RsaPublicKey::new(&[], &EXPONENT).is_err());
+        assert!(RsaPublicKey::new(&[0, 1], &EXPONENT).is_err());
+        assert!(RsaPublicKey::new(&[1; MAX_LEN + 1], &EXPONENT).is_err());
+
+        let public_key = RsaPublicKey::new(&MODULUS[..32], &EXPONENT).unwrap();
+        assert_eq!(
+            public_key.verify_pkcs1v15_sha256(&[0; HASH_BYTES], &[0; 32]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
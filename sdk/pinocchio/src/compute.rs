@@ -0,0 +1,192 @@
+//! Compute unit budget helpers.
+//!
+//! Provides access to the remaining compute units of the transaction through
+//! [`remaining`], a [`require_remaining`] check to fail early when an expensive
+//! path can not complete, and the [`cu_scope!`](crate::cu_scope) macro to profile
+//! the compute units consumed by a block of code.
+
+use crate::{program_error::ProgramError, ProgramResult};
+
+/// Compute units charged by the `sol_remaining_compute_units` syscall.
+///
+/// This is the overhead subtracted by [`consumed`] from the difference between two
+/// reads of the remaining compute units.
+pub const REMAINING_COMPUTE_UNITS_COST: u64 = 100;
+
+/// Custom error code returned by [`require_remaining`].
+///
+/// The code is at the end of the `u32` range so that it does not collide with the
+/// custom errors of programs, which usually start at `0`.
+pub const INSUFFICIENT_COMPUTE_UNITS: u32 = u32::MAX;
+
+/// Maximum length of a message logged by [`log_consumed`].
+const LOG_MESSAGE_LEN: usize = 128;
+
+/// Suffix of the message logged by [`log_consumed`].
+const LOG_SUFFIX: &[u8] = b" compute units";
+
+/// Return the remaining compute units of the transaction.
+#[inline(always)]
+pub fn remaining() -> u64 {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        crate::syscalls::sol_remaining_compute_units()
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    crate::host::sol_remaining_compute_units()
+}
+
+/// Require that at least `units` compute units remain.
+///
+/// Returns [`ProgramError::Custom`] with [`INSUFFICIENT_COMPUTE_UNITS`] if fewer
+/// compute units remain.
+#[inline]
+pub fn require_remaining(units: u64) -> ProgramResult {
+    if remaining() >= units {
+        Ok(())
+    } else {
+        Err(ProgramError::Custom(INSUFFICIENT_COMPUTE_UNITS))
+    }
+}
+
+/// Return the compute units consumed between two reads of the remaining compute
+/// units, excluding the cost of the second read.
+#[inline(always)]
+pub fn consumed(start: u64, end: u64) -> u64 {
+    start
+        .saturating_sub(end)
+        .saturating_sub(REMAINING_COMPUTE_UNITS_COST)
+}
+
+/// Log the compute units `consumed` by the code identified by `label`.
+///
+/// The message has the form `"<label> consumed <units> compute units"` and is
+/// formatted without allocating. Long labels are truncated.
+pub fn log_consumed(label: &str, consumed: u64) {
+    let mut message = [0u8; LOG_MESSAGE_LEN];
+
+    let mut units = [0u8; 20];
+    let mut start = units.len();
+    let mut value = consumed;
+    loop {
+        start -= 1;
+        units[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+
+    let tail_len = b" consumed ".len() + (units.len() - start) + LOG_SUFFIX.len();
+    let mut label_len = core::cmp::min(label.len(), LOG_MESSAGE_LEN - tail_len);
+    // truncate the label at a character boundary
+    while !label.is_char_boundary(label_len) {
+        label_len -= 1;
+    }
+
+    let mut len = 0;
+    for part in [
+        &label.as_bytes()[..label_len],
+        b" consumed ",
+        &units[start..],
+        LOG_SUFFIX,
+    ] {
+        message[len..len + part.len()].copy_from_slice(part);
+        len += part.len();
+    }
+
+    // SAFETY: The message is built from a valid UTF-8 label truncated at a
+    // character boundary and ASCII bytes.
+    crate::log::sol_log(unsafe { core::str::from_utf8_unchecked(&message[..len]) });
+}
+
+/// Measure and log the compute units consumed by a block.
+///
+/// The block is evaluated and its value returned. The compute units consumed by
+/// the block, excluding the overhead of reading the remaining compute units, are
+/// logged with the given label.
+///
+/// # Example
+///
+/// ```
+/// use pinocchio::cu_scope;
+///
+/// let sum = cu_scope!("sum", {
+///     (0..10u64).sum::<u64>()
+/// });
+/// assert_eq!(sum, 45);
+/// ```
+#[macro_export]
+macro_rules! cu_scope {
+    ( $label:expr, $body:block ) => {{
+        let start = $crate::compute::remaining();
+        let result = $body;
+        let end = $crate::compute::remaining();
+        $crate::compute::log_consumed($label, $crate::compute::consumed(start, end));
+        result
+    }};
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::host::{set_backend, TestBackend};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_remaining() {
+        let backend = Rc::new(TestBackend::default());
+        backend.remaining_compute_units.set(5_000);
+        let _guard = set_backend(backend.clone());
+
+        assert_eq!(remaining(), 5_000);
+        assert!(require_remaining(5_000).is_ok());
+        assert_eq!(
+            require_remaining(5_001),
+            Err(ProgramError::Custom(INSUFFICIENT_COMPUTE_UNITS))
+        );
+    }
+
+    #[test]
+    fn test_cu_scope() {
+        let backend = Rc::new(TestBackend::default());
+        backend.remaining_compute_units.set(10_000);
+        let _guard = set_backend(backend.clone());
+
+        let value = cu_scope!("transfer", {
+            let remaining = backend.remaining_compute_units.get();
+            backend
+                .remaining_compute_units
+                .set(remaining - 1_234 - REMAINING_COMPUTE_UNITS_COST);
+            42
+        });
+        assert_eq!(value, 42);
+
+        cu_scope!("noop", {});
+
+        assert_eq!(
+            backend.logs(),
+            [
+                "transfer consumed 1234 compute units",
+                "noop consumed 0 compute units"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_log_consumed() {
+        let backend = Rc::new(TestBackend::default());
+        let _guard = set_backend(backend.clone());
+
+        // the label is truncated at a character boundary
+        let label = std::format!("a{}", "é".repeat(100));
+        log_consumed(&label, u64::MAX);
+
+        let logs = backend.logs();
+        assert_eq!(logs[0].len(), LOG_MESSAGE_LEN - 1);
+        assert!(logs[0].ends_with(" consumed 18446744073709551615 compute units"));
+    }
+}
@@ -7,12 +7,18 @@ use crate::{
     instruction::{Account, AccountMeta, Instruction, Signer},
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvars::instructions::Instructions,
     ProgramResult,
 };
 
 /// Maximum number of accounts that can be passed to a cross-program invocation.
 pub const MAX_CPI_ACCOUNTS: usize = 64;
 
+/// Stack height of an instruction invoked directly by the transaction.
+///
+/// Each cross-program invocation increases the stack height by one.
+pub const TRANSACTION_LEVEL_STACK_HEIGHT: usize = 1;
+
 /// An `Instruction` as expected by `sol_invoke_signed_c`.
 ///
 /// DO NOT EXPOSE THIS STRUCT:
@@ -250,7 +256,9 @@ pub fn set_return_data(data: &[u8]) {
     };
 
     #[cfg(not(target_os = "solana"))]
-    core::hint::black_box(data);
+    unsafe {
+        crate::host::sol_set_return_data(data.as_ptr(), data.len() as u64)
+    };
 }
 
 /// Get the return data from an invoked program.
@@ -283,33 +291,37 @@ pub fn set_return_data(data: &[u8]) {
 ///
 /// [rdp]: https://docs.solanalabs.com/proposals/return-data
 pub fn get_return_data() -> Option<ReturnData> {
-    #[cfg(target_os = "solana")]
-    {
-        const UNINIT_BYTE: core::mem::MaybeUninit<u8> = core::mem::MaybeUninit::<u8>::uninit();
-        let mut data = [UNINIT_BYTE; MAX_RETURN_DATA];
-        let mut program_id = Pubkey::default();
-
-        let size = unsafe {
-            crate::syscalls::sol_get_return_data(
-                data.as_mut_ptr() as *mut u8,
-                data.len() as u64,
-                &mut program_id,
-            )
-        };
+    const UNINIT_BYTE: core::mem::MaybeUninit<u8> = core::mem::MaybeUninit::<u8>::uninit();
+    let mut data = [UNINIT_BYTE; MAX_RETURN_DATA];
+    let mut program_id = Pubkey::default();
 
-        if size == 0 {
-            None
-        } else {
-            Some(ReturnData {
-                program_id,
-                data,
-                size: core::cmp::min(size as usize, MAX_RETURN_DATA),
-            })
-        }
-    }
+    #[cfg(target_os = "solana")]
+    let size = unsafe {
+        crate::syscalls::sol_get_return_data(
+            data.as_mut_ptr() as *mut u8,
+            data.len() as u64,
+            &mut program_id,
+        )
+    };
 
     #[cfg(not(target_os = "solana"))]
-    core::hint::black_box(None)
+    let size = unsafe {
+        crate::host::sol_get_return_data(
+            data.as_mut_ptr() as *mut u8,
+            data.len() as u64,
+            &mut program_id,
+        )
+    };
+
+    if size == 0 {
+        None
+    } else {
+        Some(ReturnData {
+            program_id,
+            data,
+            size: core::cmp::min(size as usize, MAX_RETURN_DATA),
+        })
+    }
 }
 
 /// Struct to hold the return data from an invoked program.
@@ -343,3 +355,138 @@ impl Deref for ReturnData {
         self.as_slice()
     }
 }
+
+/// Return the current stack height.
+///
+/// Instructions invoked directly by the transaction run at
+/// [`TRANSACTION_LEVEL_STACK_HEIGHT`], and each cross-program invocation increases
+/// the stack height by one.
+#[inline(always)]
+pub fn stack_height() -> usize {
+    #[cfg(target_os = "solana")]
+    let height = unsafe { crate::syscalls::sol_get_stack_height() };
+
+    #[cfg(not(target_os = "solana"))]
+    let height = crate::host::sol_get_stack_height();
+
+    height as usize
+}
+
+/// Require that the current instruction was invoked directly by the transaction.
+///
+/// This rejects cross-program invocations, including reentrant calls.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the instruction was invoked through
+/// a cross-program invocation.
+#[inline]
+pub fn require_top_level() -> ProgramResult {
+    if stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+/// Require that the current instruction was invoked through a cross-program
+/// invocation.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the instruction was invoked directly
+/// by the transaction.
+#[inline]
+pub fn require_invoked_via_cpi() -> ProgramResult {
+    if stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+/// Require that the current instruction was invoked through a cross-program
+/// invocation by `program_id`, where `program_id` is the program of the current
+/// transaction-level instruction.
+///
+/// The current instruction of the `Instructions` sysvar is the transaction-level
+/// instruction, so this requires the stack height to be exactly one above
+/// [`TRANSACTION_LEVEL_STACK_HEIGHT`] to ensure that `program_id` is the direct
+/// caller.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the instruction was not invoked by
+/// a transaction-level instruction through a cross-program invocation, and
+/// [`ProgramError::IncorrectProgramId`] if the transaction-level instruction does
+/// not invoke `program_id`.
+pub fn require_invoked_via_cpi_from<T>(
+    instructions: &Instructions<T>,
+    program_id: &Pubkey,
+) -> ProgramResult
+where
+    T: Deref<Target = [u8]>,
+{
+    if stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT + 1 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let current = instructions.load_instruction_at(instructions.load_current_index() as usize)?;
+
+    if current.get_program_id() == program_id {
+        Ok(())
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::{
+        host::{set_backend, TestBackend},
+        sysvars::instructions::InstructionsBuilder,
+    };
+    use std::rc::Rc;
+
+    #[test]
+    fn test_stack_height() {
+        assert_eq!(stack_height(), 1);
+
+        let backend = Rc::new(TestBackend::default());
+        let _guard = set_backend(backend.clone());
+
+        let data = InstructionsBuilder::new()
+            .instruction(&Instruction {
+                program_id: &[1u8; 32],
+                data: &[],
+                accounts: &[],
+            })
+            .build();
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+
+        assert!(require_top_level().is_ok());
+        assert_eq!(
+            require_invoked_via_cpi(),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            require_invoked_via_cpi_from(&instructions, &[1u8; 32]),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        backend.stack_height.set(2);
+
+        assert_eq!(require_top_level(), Err(ProgramError::InvalidArgument));
+        assert!(require_invoked_via_cpi().is_ok());
+        assert!(require_invoked_via_cpi_from(&instructions, &[1u8; 32]).is_ok());
+        assert_eq!(
+            require_invoked_via_cpi_from(&instructions, &[2u8; 32]),
+            Err(ProgramError::IncorrectProgramId)
+        );
+
+        backend.stack_height.set(3);
+
+        assert!(require_invoked_via_cpi().is_ok());
+        assert_eq!(
+            require_invoked_via_cpi_from(&instructions, &[1u8; 32]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
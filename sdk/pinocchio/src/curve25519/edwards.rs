@@ -0,0 +1,19 @@
+//! Operations on the Edwards representation of Curve25519.
+
+use super::{impl_curve, PodScalar, CURVE25519_EDWARDS, POD_BYTES};
+use crate::program_error::ProgramError;
+
+/// A point of the Edwards curve, in its compressed 32-byte encoding.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PodEdwardsPoint(pub [u8; POD_BYTES]);
+
+impl_curve!(
+    PodEdwardsPoint,
+    CURVE25519_EDWARDS,
+    validate_edwards,
+    add_edwards,
+    subtract_edwards,
+    multiply_edwards,
+    multiscalar_multiply_edwards
+);
@@ -0,0 +1,399 @@
+//! Curve25519 operations.
+//!
+//! Provides typed wrappers over the `sol_curve_*` syscalls for the Edwards
+//! ([`edwards`]) and Ristretto ([`ristretto`]) representations of Curve25519.
+//! Points and scalars are represented as "pod" types, i.e., their 32-byte
+//! encodings, so they can be read directly from account or instruction data.
+//!
+//! The curve operations are only available on-chain. When not compiled for the
+//! `solana` target, all operations fail and points are reported as invalid, unless
+//! they are implemented by the host backend of a native test.
+
+use crate::program_error::ProgramError;
+
+pub mod edwards;
+pub mod ristretto;
+
+/// Identifier of the Edwards representation of Curve25519.
+pub const CURVE25519_EDWARDS: u64 = 0;
+
+/// Identifier of the Ristretto representation of Curve25519.
+pub const CURVE25519_RISTRETTO: u64 = 1;
+
+/// Identifier of the addition group operation.
+pub const ADD: u64 = 0;
+
+/// Identifier of the subtraction group operation.
+pub const SUB: u64 = 1;
+
+/// Identifier of the scalar multiplication group operation.
+pub const MUL: u64 = 2;
+
+/// Number of bytes of an encoded point or scalar.
+pub const POD_BYTES: usize = 32;
+
+/// Maximum number of points accepted by a multiscalar multiplication.
+pub const MAX_MULTISCALAR_POINTS: usize = 512;
+
+/// A scalar of the Curve25519 group, in little-endian canonical form.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PodScalar(pub [u8; POD_BYTES]);
+
+impl From<[u8; POD_BYTES]> for PodScalar {
+    #[inline(always)]
+    fn from(bytes: [u8; POD_BYTES]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// Checks whether `point` is a valid encoding of a point of the curve `curve_id`.
+#[inline]
+fn validate_point(curve_id: u64, point: &[u8; POD_BYTES]) -> bool {
+    let mut validate_result = 0u8;
+
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        crate::syscalls::sol_curve_validate_point(
+            curve_id,
+            point as *const _ as *const u8,
+            &mut validate_result,
+        )
+    };
+
+    #[cfg(not(target_os = "solana"))]
+    let result = unsafe {
+        crate::host::sol_curve_validate_point(
+            curve_id,
+            point as *const _ as *const u8,
+            &mut validate_result,
+        )
+    };
+
+    result == crate::SUCCESS
+}
+
+/// Performs the group operation `group_op` of the curve `curve_id`.
+///
+/// For scalar multiplication, `left` is the scalar and `right` is the point.
+#[inline]
+fn group_op(
+    curve_id: u64,
+    group_op: u64,
+    left: &[u8; POD_BYTES],
+    right: &[u8; POD_BYTES],
+) -> Result<[u8; POD_BYTES], ProgramError> {
+    let mut result_point = core::mem::MaybeUninit::<[u8; POD_BYTES]>::uninit();
+
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        crate::syscalls::sol_curve_group_op(
+            curve_id,
+            group_op,
+            left as *const _ as *const u8,
+            right as *const _ as *const u8,
+            result_point.as_mut_ptr() as *mut u8,
+        )
+    };
+
+    #[cfg(not(target_os = "solana"))]
+    let result = unsafe {
+        crate::host::sol_curve_group_op(
+            curve_id,
+            group_op,
+            left as *const _ as *const u8,
+            right as *const _ as *const u8,
+            result_point.as_mut_ptr() as *mut u8,
+        )
+    };
+
+    match result {
+        // SAFETY: The syscall writes the resulting point on success.
+        crate::SUCCESS => Ok(unsafe { result_point.assume_init() }),
+        _ => Err(ProgramError::InvalidArgument),
+    }
+}
+
+/// Computes the multiscalar multiplication of `scalars` and the `points_len` points
+/// at `points` of the curve `curve_id`.
+///
+/// # Safety
+///
+/// `points` must point to `points_len` contiguous encoded points.
+#[inline]
+unsafe fn multiscalar_multiply(
+    curve_id: u64,
+    scalars: &[PodScalar],
+    points: *const u8,
+    points_len: usize,
+) -> Result<[u8; POD_BYTES], ProgramError> {
+    if scalars.len() != points_len || points_len > MAX_MULTISCALAR_POINTS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut result_point = core::mem::MaybeUninit::<[u8; POD_BYTES]>::uninit();
+
+    #[cfg(target_os = "solana")]
+    let result = crate::syscalls::sol_curve_multiscalar_mul(
+        curve_id,
+        scalars as *const _ as *const u8,
+        points,
+        points_len as u64,
+        result_point.as_mut_ptr() as *mut u8,
+    );
+
+    #[cfg(not(target_os = "solana"))]
+    let result = crate::host::sol_curve_multiscalar_mul(
+        curve_id,
+        scalars as *const _ as *const u8,
+        points,
+        points_len as u64,
+        result_point.as_mut_ptr() as *mut u8,
+    );
+
+    match result {
+        // SAFETY: The syscall writes the resulting point on success.
+        crate::SUCCESS => Ok(result_point.assume_init()),
+        _ => Err(ProgramError::InvalidArgument),
+    }
+}
+
+/// Implements the operations of a Curve25519 representation.
+///
+/// The calling module must import `PodScalar`, `POD_BYTES` and `ProgramError`.
+macro_rules! impl_curve {
+    (
+        $point:ident,
+        $curve_id:expr,
+        $validate:ident,
+        $add:ident,
+        $subtract:ident,
+        $multiply:ident,
+        $multiscalar_multiply:ident
+    ) => {
+        impl From<[u8; POD_BYTES]> for $point {
+            #[inline(always)]
+            fn from(bytes: [u8; POD_BYTES]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        /// Checks whether `point` is a valid encoding of a curve point.
+        #[inline]
+        pub fn $validate(point: &$point) -> bool {
+            super::validate_point($curve_id, &point.0)
+        }
+
+        /// Adds two points.
+        ///
+        /// Returns [`ProgramError::InvalidArgument`] if any of the points is invalid.
+        #[inline]
+        pub fn $add(left: &$point, right: &$point) -> Result<$point, ProgramError> {
+            super::group_op($curve_id, super::ADD, &left.0, &right.0).map($point)
+        }
+
+        /// Subtracts `right` from `left`.
+        ///
+        /// Returns [`ProgramError::InvalidArgument`] if any of the points is invalid.
+        #[inline]
+        pub fn $subtract(left: &$point, right: &$point) -> Result<$point, ProgramError> {
+            super::group_op($curve_id, super::SUB, &left.0, &right.0).map($point)
+        }
+
+        /// Multiplies a point by a scalar.
+        ///
+        /// Returns [`ProgramError::InvalidArgument`] if the point is invalid or the
+        /// scalar is not canonical.
+        #[inline]
+        pub fn $multiply(scalar: &PodScalar, point: &$point) -> Result<$point, ProgramError> {
+            super::group_op($curve_id, super::MUL, &scalar.0, &point.0).map($point)
+        }
+
+        /// Computes the sum of the products of each scalar and its corresponding point.
+        ///
+        /// Returns [`ProgramError::InvalidArgument`] if the number of scalars and points
+        /// differ, there are more than [`MAX_MULTISCALAR_POINTS`](super::MAX_MULTISCALAR_POINTS)
+        /// points, any point is invalid or any scalar is not canonical.
+        #[inline]
+        pub fn $multiscalar_multiply(
+            scalars: &[PodScalar],
+            points: &[$point],
+        ) -> Result<$point, ProgramError> {
+            // SAFETY: The point type is a transparent wrapper over its encoding.
+            unsafe {
+                super::multiscalar_multiply(
+                    $curve_id,
+                    scalars,
+                    points as *const _ as *const u8,
+                    points.len(),
+                )
+            }
+            .map($point)
+        }
+    };
+}
+
+use impl_curve;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        edwards::{self, PodEdwardsPoint},
+        ristretto::{self, PodRistrettoPoint},
+        PodScalar, MAX_MULTISCALAR_POINTS, POD_BYTES,
+    };
+    use crate::program_error::ProgramError;
+
+    #[test]
+    fn test_multiscalar_multiply_lengths() {
+        let scalars = [PodScalar([1; POD_BYTES]); 2];
+        let points = [PodEdwardsPoint([2; POD_BYTES]); 3];
+
+        assert_eq!(
+            edwards::multiscalar_multiply_edwards(&scalars, &points),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            ristretto::multiscalar_multiply_ristretto(&scalars, &[PodRistrettoPoint::default()]),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        let scalars = [PodScalar::default(); MAX_MULTISCALAR_POINTS + 1];
+        let points = [PodEdwardsPoint::default(); MAX_MULTISCALAR_POINTS + 1];
+        assert_eq!(
+            edwards::multiscalar_multiply_edwards(&scalars, &points),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_syscall_failure() {
+        let point = PodEdwardsPoint([2; POD_BYTES]);
+        let scalar = PodScalar([1; POD_BYTES]);
+
+        // without a backend, the host syscalls always fail
+        assert!(!edwards::validate_edwards(&point));
+        assert_eq!(
+            edwards::add_edwards(&point, &point),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            edwards::subtract_edwards(&point, &point),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            edwards::multiply_edwards(&scalar, &point),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            edwards::multiscalar_multiply_edwards(&[scalar], &[point]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_syscall_arguments() {
+        extern crate std;
+
+        use super::{ADD, CURVE25519_EDWARDS, CURVE25519_RISTRETTO, MUL, SUB};
+        use crate::host::{set_backend, Backend};
+        use std::{cell::RefCell, rc::Rc, vec::Vec};
+
+        /// A backend recording the calls and returning the first byte of each
+        /// argument in the resulting point.
+        #[derive(Default)]
+        struct RecordingBackend {
+            calls: RefCell<Vec<(u64, u64, usize)>>,
+        }
+
+        impl Backend for RecordingBackend {
+            fn curve_validate_point(&self, curve_id: u64, point: &[u8; 32]) -> bool {
+                curve_id == CURVE25519_RISTRETTO && point[0] == 1
+            }
+
+            fn curve_group_op(
+                &self,
+                curve_id: u64,
+                group_op: u64,
+                left: &[u8; 32],
+                right: &[u8; 32],
+            ) -> Option<[u8; 32]> {
+                self.calls.borrow_mut().push((curve_id, group_op, 1));
+                let mut result = [0; 32];
+                result[0] = left[0];
+                result[1] = right[0];
+                Some(result)
+            }
+
+            fn curve_multiscalar_mul(
+                &self,
+                curve_id: u64,
+                scalars: &[[u8; 32]],
+                points: &[[u8; 32]],
+            ) -> Option<[u8; 32]> {
+                self.calls
+                    .borrow_mut()
+                    .push((curve_id, u64::MAX, scalars.len()));
+                let mut result = [0; 32];
+                for (i, (scalar, point)) in scalars.iter().zip(points).enumerate() {
+                    result[2 * i] = scalar[0];
+                    result[2 * i + 1] = point[0];
+                }
+                Some(result)
+            }
+        }
+
+        let backend = Rc::new(RecordingBackend::default());
+        let _guard = set_backend(backend.clone());
+
+        let mut left = PodRistrettoPoint::default();
+        left.0[0] = 1;
+        let mut right = PodRistrettoPoint::default();
+        right.0[0] = 2;
+        let mut scalar = PodScalar::default();
+        scalar.0[0] = 3;
+
+        assert!(ristretto::validate_ristretto(&left));
+        assert!(!ristretto::validate_ristretto(&right));
+        assert!(!edwards::validate_edwards(&PodEdwardsPoint(left.0)));
+
+        assert_eq!(
+            ristretto::add_ristretto(&left, &right).unwrap().0[..2],
+            [1, 2]
+        );
+        assert_eq!(
+            ristretto::subtract_ristretto(&right, &left).unwrap().0[..2],
+            [2, 1]
+        );
+        assert_eq!(
+            ristretto::multiply_ristretto(&scalar, &left).unwrap().0[..2],
+            [3, 1]
+        );
+        assert_eq!(
+            edwards::multiscalar_multiply_edwards(
+                &[scalar, PodScalar([4; POD_BYTES])],
+                &[PodEdwardsPoint(right.0), PodEdwardsPoint([5; POD_BYTES])]
+            )
+            .unwrap()
+            .0[..4],
+            [3, 2, 4, 5]
+        );
+
+        // mismatched lengths are rejected before the syscall
+        assert_eq!(
+            edwards::multiscalar_multiply_edwards(&[scalar], &[]),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        assert_eq!(
+            *backend.calls.borrow(),
+            [
+                (CURVE25519_RISTRETTO, ADD, 1),
+                (CURVE25519_RISTRETTO, SUB, 1),
+                (CURVE25519_RISTRETTO, MUL, 1),
+                (CURVE25519_EDWARDS, u64::MAX, 2),
+            ]
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Operations on the Ristretto group over Curve25519.
+
+use super::{impl_curve, PodScalar, CURVE25519_RISTRETTO, POD_BYTES};
+use crate::program_error::ProgramError;
+
+/// A point of the Ristretto group, in its 32-byte encoding.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PodRistrettoPoint(pub [u8; POD_BYTES]);
+
+impl_curve!(
+    PodRistrettoPoint,
+    CURVE25519_RISTRETTO,
+    validate_ristretto,
+    add_ristretto,
+    subtract_ristretto,
+    multiply_ristretto,
+    multiscalar_multiply_ristretto
+);
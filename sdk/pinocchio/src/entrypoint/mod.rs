@@ -4,6 +4,11 @@
 pub mod lazy;
 pub use lazy::{InstructionContext, MaybeAccount};
 
+#[cfg(feature = "std")]
+pub mod serializer;
+#[cfg(feature = "std")]
+pub use serializer::{InputAccount, InputBuilder, SerializedInput};
+
 #[cfg(target_os = "solana")]
 pub use alloc::BumpAllocator;
 
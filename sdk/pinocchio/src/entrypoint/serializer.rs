@@ -0,0 +1,461 @@
+//! Host-side serialization of the program input buffer.
+//!
+//! The SVM loader serializes the program id, accounts and instruction data into a
+//! single byte array before calling the program entrypoint. This module lays out
+//! the same (aligned) input on the host, so that [`deserialize`](super::deserialize)
+//! and [`InstructionContext`](super::InstructionContext) can be exercised in native
+//! tests, and provides read-back accessors to inspect the accounts after the program
+//! has executed.
+//!
+//! # Example
+//!
+//! ```
+//! use core::mem::MaybeUninit;
+//! use pinocchio::{
+//!     account_info::AccountInfo,
+//!     entrypoint::serializer::{InputAccount, InputBuilder},
+//! };
+//!
+//! let program_id = [1u8; 32];
+//! let mut input = InputBuilder::new(&program_id)
+//!     .account(InputAccount::new([2u8; 32], program_id).lamports(1_000).writable())
+//!     .duplicate(0)
+//!     .instruction_data(&[1, 2, 3])
+//!     .build();
+//!
+//! const UNINIT: MaybeUninit<AccountInfo> = MaybeUninit::<AccountInfo>::uninit();
+//! let mut accounts = [UNINIT; 2];
+//! let (id, count, data) =
+//!     unsafe { pinocchio::entrypoint::deserialize::<2>(input.as_mut_ptr(), &mut accounts) };
+//!
+//! assert_eq!(id, &program_id);
+//! assert_eq!(count, 2);
+//! assert_eq!(data, &[1, 2, 3]);
+//! assert_eq!(input.account(1).unwrap().lamports(), 1_000);
+//! ```
+
+use std::vec::Vec;
+
+use crate::{
+    account_info::{Account, MAX_PERMITTED_DATA_INCREASE},
+    pubkey::{Pubkey, PUBKEY_BYTES},
+    BPF_ALIGN_OF_U128, NON_DUP_MARKER,
+};
+
+/// Offset of the `owner` field in the serialized account header.
+const OWNER_OFFSET: usize = 40;
+
+/// Offset of the `lamports` field in the serialized account header.
+const LAMPORTS_OFFSET: usize = 72;
+
+/// Offset of the `data_len` field in the serialized account header.
+const DATA_LEN_OFFSET: usize = 80;
+
+/// Description of a (non-duplicated) account to be serialized.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputAccount {
+    /// Public key of the account.
+    pub key: Pubkey,
+
+    /// Program that owns the account.
+    pub owner: Pubkey,
+
+    /// The lamports in the account.
+    pub lamports: u64,
+
+    /// Data of the account.
+    pub data: Vec<u8>,
+
+    /// Indicates whether the transaction was signed by this account.
+    pub is_signer: bool,
+
+    /// Indicates whether the account is writable.
+    pub is_writable: bool,
+
+    /// Indicates whether this account represents a program.
+    pub executable: bool,
+
+    /// The epoch at which this account will next owe rent.
+    pub rent_epoch: u64,
+}
+
+impl InputAccount {
+    /// Creates a new read-only, non-signer `InputAccount` with no lamports
+    /// and empty data.
+    pub fn new(key: Pubkey, owner: Pubkey) -> Self {
+        Self {
+            key,
+            owner,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the lamports of the account.
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    /// Sets the data of the account.
+    pub fn data(mut self, data: &[u8]) -> Self {
+        self.data = data.to_vec();
+        self
+    }
+
+    /// Marks the account as a signer.
+    pub fn signer(mut self) -> Self {
+        self.is_signer = true;
+        self
+    }
+
+    /// Marks the account as writable.
+    pub fn writable(mut self) -> Self {
+        self.is_writable = true;
+        self
+    }
+
+    /// Marks the account as executable.
+    pub fn executable(mut self) -> Self {
+        self.executable = true;
+        self
+    }
+
+    /// Sets the rent epoch of the account.
+    pub fn rent_epoch(mut self, rent_epoch: u64) -> Self {
+        self.rent_epoch = rent_epoch;
+        self
+    }
+}
+
+/// An entry of the account list to be serialized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Entry {
+    /// A non-duplicated account.
+    Account(InputAccount),
+
+    /// The index of the original account that is duplicated.
+    Duplicated(u8),
+}
+
+/// Builder for the program input buffer.
+///
+/// The resulting buffer has the same layout as the one produced by the aligned
+/// loader (`BPFLoaderUpgradeable` and `BPFLoader2`).
+#[derive(Clone, Debug, Default)]
+pub struct InputBuilder {
+    /// Public key of the program being invoked.
+    program_id: Pubkey,
+
+    /// Accounts of the instruction.
+    accounts: Vec<Entry>,
+
+    /// Data of the instruction.
+    instruction_data: Vec<u8>,
+}
+
+impl InputBuilder {
+    /// Creates a new `InputBuilder` for the given program id.
+    pub fn new(program_id: &Pubkey) -> Self {
+        Self {
+            program_id: *program_id,
+            ..Self::default()
+        }
+    }
+
+    /// Appends an account to the input.
+    pub fn account(mut self, account: InputAccount) -> Self {
+        self.accounts.push(Entry::Account(account));
+        self
+    }
+
+    /// Appends a duplicate of the account at position `index` to the input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` does not refer to a previously added account.
+    pub fn duplicate(mut self, index: u8) -> Self {
+        assert!(
+            (index as usize) < self.accounts.len(),
+            "duplicate index out of bounds"
+        );
+        // The loader always references the first occurrence of an account.
+        let original = match self.accounts[index as usize] {
+            Entry::Account(_) => index,
+            Entry::Duplicated(original) => original,
+        };
+        self.accounts.push(Entry::Duplicated(original));
+        self
+    }
+
+    /// Sets the instruction data.
+    pub fn instruction_data(mut self, data: &[u8]) -> Self {
+        self.instruction_data = data.to_vec();
+        self
+    }
+
+    /// Serializes the input.
+    pub fn build(self) -> SerializedInput {
+        let mut bytes = Vec::new();
+        let mut offsets = Vec::with_capacity(self.accounts.len());
+
+        bytes.extend_from_slice(&(self.accounts.len() as u64).to_le_bytes());
+
+        for entry in &self.accounts {
+            match entry {
+                Entry::Account(account) => {
+                    offsets.push(bytes.len());
+
+                    bytes.push(NON_DUP_MARKER);
+                    bytes.push(account.is_signer as u8);
+                    bytes.push(account.is_writable as u8);
+                    bytes.push(account.executable as u8);
+                    // original data length (padding for the loader)
+                    bytes.extend_from_slice(&[0u8; 4]);
+                    bytes.extend_from_slice(&account.key);
+                    bytes.extend_from_slice(&account.owner);
+                    bytes.extend_from_slice(&account.lamports.to_le_bytes());
+                    bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+                    bytes.extend_from_slice(&account.data);
+                    bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+                    bytes.resize(align(bytes.len()), 0);
+                    bytes.extend_from_slice(&account.rent_epoch.to_le_bytes());
+                }
+                Entry::Duplicated(index) => {
+                    offsets.push(offsets[*index as usize]);
+
+                    bytes.push(*index);
+                    bytes.extend_from_slice(&[0u8; 7]);
+                }
+            }
+        }
+
+        bytes.extend_from_slice(&(self.instruction_data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.instruction_data);
+        bytes.extend_from_slice(&self.program_id);
+
+        // Copy the bytes into a `u64` backed buffer to guarantee the alignment
+        // expected by the entrypoint.
+        let len = bytes.len();
+        let mut buffer = std::vec![0u64; len.div_ceil(core::mem::size_of::<u64>())];
+        // SAFETY: `buffer` has at least `len` bytes.
+        unsafe {
+            core::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer.as_mut_ptr() as *mut u8, len);
+        }
+
+        SerializedInput {
+            buffer,
+            len,
+            offsets,
+        }
+    }
+}
+
+/// A serialized program input buffer.
+///
+/// The buffer can be passed to the entrypoint [`deserialize`](super::deserialize)
+/// function or to [`InstructionContext::new`](super::InstructionContext::new). Since
+/// both modify the buffer in place, the input should only be deserialized once.
+pub struct SerializedInput {
+    /// Aligned storage for the input bytes.
+    buffer: Vec<u64>,
+
+    /// Length of the input in bytes.
+    len: usize,
+
+    /// Offset of the account header for each account of the input.
+    ///
+    /// Duplicated accounts point to the offset of the original account.
+    offsets: Vec<usize>,
+}
+
+impl SerializedInput {
+    /// Returns a mutable pointer to the start of the input buffer.
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.buffer.as_mut_ptr() as *mut u8
+    }
+
+    /// Returns the serialized bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `buffer` has at least `len` initialized bytes.
+        unsafe { core::slice::from_raw_parts(self.buffer.as_ptr() as *const u8, self.len) }
+    }
+
+    /// Returns the number of accounts in the input.
+    pub fn accounts_len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns a view over the serialized account at position `index`.
+    ///
+    /// Duplicated accounts resolve to their original account. The view reflects
+    /// any modification made to the account (lamports, data, owner and data length)
+    /// while the program executed.
+    pub fn account(&self, index: usize) -> Option<SerializedAccount> {
+        self.offsets.get(index).map(|offset| SerializedAccount {
+            header: &self.as_slice()[*offset..],
+        })
+    }
+}
+
+/// A view over an account of a [`SerializedInput`].
+pub struct SerializedAccount<'a> {
+    /// Bytes of the input buffer starting at the account header.
+    header: &'a [u8],
+}
+
+impl SerializedAccount<'_> {
+    /// Indicates whether the transaction was signed by this account.
+    pub fn is_signer(&self) -> bool {
+        self.header[1] != 0
+    }
+
+    /// Indicates whether the account is writable.
+    pub fn is_writable(&self) -> bool {
+        self.header[2] != 0
+    }
+
+    /// Indicates whether this account represents a program.
+    pub fn executable(&self) -> bool {
+        self.header[3] != 0
+    }
+
+    /// Public key of the account.
+    pub fn key(&self) -> &Pubkey {
+        self.pubkey_at(8)
+    }
+
+    /// Program that owns the account.
+    pub fn owner(&self) -> &Pubkey {
+        self.pubkey_at(OWNER_OFFSET)
+    }
+
+    /// The lamports in the account.
+    pub fn lamports(&self) -> u64 {
+        self.u64_at(LAMPORTS_OFFSET)
+    }
+
+    /// Current length of the account data.
+    pub fn data_len(&self) -> usize {
+        self.u64_at(DATA_LEN_OFFSET) as usize
+    }
+
+    /// Current data of the account.
+    pub fn data(&self) -> &[u8] {
+        let start = core::mem::size_of::<Account>();
+        &self.header[start..start + self.data_len()]
+    }
+
+    #[inline(always)]
+    fn pubkey_at(&self, offset: usize) -> &Pubkey {
+        self.header[offset..offset + PUBKEY_BYTES]
+            .try_into()
+            .unwrap()
+    }
+
+    #[inline(always)]
+    fn u64_at(&self, offset: usize) -> u64 {
+        u64::from_le_bytes(self.header[offset..offset + 8].try_into().unwrap())
+    }
+}
+
+/// Rounds `offset` up to the alignment used by the loader.
+#[inline(always)]
+const fn align(offset: usize) -> usize {
+    (offset + (BPF_ALIGN_OF_U128 - 1)) & !(BPF_ALIGN_OF_U128 - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::MaybeUninit;
+
+    use super::*;
+    use crate::{
+        account_info::AccountInfo,
+        entrypoint::{deserialize, InstructionContext, MaybeAccount},
+    };
+
+    const PROGRAM_ID: Pubkey = [7u8; 32];
+
+    fn input() -> SerializedInput {
+        InputBuilder::new(&PROGRAM_ID)
+            .account(
+                InputAccount::new([1u8; 32], PROGRAM_ID)
+                    .lamports(500)
+                    .data(&[1, 2, 3])
+                    .signer()
+                    .writable(),
+            )
+            .account(InputAccount::new([2u8; 32], [0u8; 32]).executable())
+            .duplicate(0)
+            .instruction_data(&[9, 8, 7, 6])
+            .build()
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let mut input = input();
+        const UNINIT: MaybeUninit<AccountInfo> = MaybeUninit::<AccountInfo>::uninit();
+        let mut accounts = [UNINIT; 3];
+
+        let (program_id, count, data) =
+            unsafe { deserialize::<3>(input.as_mut_ptr(), &mut accounts) };
+
+        assert_eq!(program_id, &PROGRAM_ID);
+        assert_eq!(count, 3);
+        assert_eq!(data, &[9, 8, 7, 6]);
+
+        let accounts: &[AccountInfo] =
+            unsafe { core::slice::from_raw_parts(accounts.as_ptr() as _, count) };
+
+        assert_eq!(accounts[0].key(), &[1u8; 32]);
+        assert!(accounts[0].is_signer());
+        assert!(accounts[0].is_writable());
+        assert!(!accounts[0].executable());
+        assert_eq!(accounts[0].lamports(), 500);
+        assert_eq!(&*accounts[0].try_borrow_data().unwrap(), &[1, 2, 3]);
+
+        assert_eq!(accounts[1].key(), &[2u8; 32]);
+        assert!(accounts[1].executable());
+        assert!(accounts[1].data_is_empty());
+
+        assert!(accounts[2] == accounts[0]);
+
+        // Modify the account and read the changes back.
+
+        *accounts[2].try_borrow_mut_lamports().unwrap() = 100;
+        accounts[0].realloc(5, true).unwrap();
+        accounts[0].try_borrow_mut_data().unwrap()[4] = 42;
+        unsafe { accounts[0].assign(&[3u8; 32]) };
+
+        let account = input.account(0).unwrap();
+        assert_eq!(account.lamports(), 100);
+        assert_eq!(account.data_len(), 5);
+        assert_eq!(account.data(), &[1, 2, 3, 0, 42]);
+        assert_eq!(account.owner(), &[3u8; 32]);
+        assert_eq!(input.account(2).unwrap().key(), account.key());
+    }
+
+    #[test]
+    fn test_lazy_context() {
+        let mut input = input();
+        let mut context = InstructionContext::new(input.as_mut_ptr());
+
+        assert_eq!(context.available(), 3);
+
+        let MaybeAccount::Account(first) = context.next_account().unwrap() else {
+            panic!("expected account");
+        };
+        assert_eq!(first.key(), &[1u8; 32]);
+
+        let second = context.next_account().unwrap().assume_account();
+        assert_eq!(second.key(), &[2u8; 32]);
+
+        let MaybeAccount::Duplicated(index) = context.next_account().unwrap() else {
+            panic!("expected duplicated account");
+        };
+        assert_eq!(index, 0);
+
+        assert_eq!(context.instruction_data().unwrap(), &[9, 8, 7, 6]);
+        assert_eq!(context.program_id().unwrap(), &PROGRAM_ID);
+    }
+}
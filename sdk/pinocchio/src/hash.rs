@@ -0,0 +1,261 @@
+//! Hashing functions.
+//!
+//! The functions in this module compute the hash of the concatenation of a list
+//! of byte slices using a single syscall: [`sha256`], [`keccak256`] and [`blake3`].
+//! The [`Hasher`] type can be used to collect the slices incrementally before
+//! computing the hash.
+//!
+//! When not compiled for the `solana` target, the hashes are computed using the
+//! pure Rust implementations of the [`host`](crate::host) module, so the same code
+//! can be used in native tests.
+//!
+//! # Example
+//!
+//! ```
+//! use pinocchio::hash::{sha256, Hash, Hasher};
+//!
+//! let mut hasher = Hasher::<2>::sha256();
+//! hasher.hash(b"hello").unwrap();
+//! hasher.hash(b"world").unwrap();
+//!
+//! assert_eq!(hasher.result(), Hash::new(sha256(&[b"hello", b"world"])));
+//! ```
+
+use crate::program_error::ProgramError;
+
+/// Number of bytes in a hash.
+pub const HASH_BYTES: usize = 32;
+
+/// A hash value.
+///
+/// Equality comparison between two `Hash` values is performed in constant time,
+/// so it is suitable to compare secret-dependent values.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hash(pub [u8; HASH_BYTES]);
+
+impl Hash {
+    /// Creates a new `Hash` from its bytes.
+    #[inline(always)]
+    pub const fn new(bytes: [u8; HASH_BYTES]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the bytes of the hash.
+    #[inline(always)]
+    pub const fn to_bytes(self) -> [u8; HASH_BYTES] {
+        self.0
+    }
+
+    /// Returns a reference to the bytes of the hash.
+    #[inline(always)]
+    pub const fn as_bytes(&self) -> &[u8; HASH_BYTES] {
+        &self.0
+    }
+}
+
+impl PartialEq for Hash {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        let mut difference = 0u8;
+        for (a, b) in self.0.iter().zip(other.0.iter()) {
+            difference |= a ^ b;
+        }
+        // prevent the compiler from short-circuiting the comparison
+        core::hint::black_box(difference) == 0
+    }
+}
+
+impl Eq for Hash {}
+
+impl From<[u8; HASH_BYTES]> for Hash {
+    #[inline(always)]
+    fn from(bytes: [u8; HASH_BYTES]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Hash> for [u8; HASH_BYTES] {
+    #[inline(always)]
+    fn from(hash: Hash) -> Self {
+        hash.0
+    }
+}
+
+impl AsRef<[u8]> for Hash {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Implements a hash function over the corresponding syscall.
+macro_rules! impl_hash {
+    ( $(#[$meta:meta])* $name:ident, $syscall_name:ident ) => {
+        $(#[$meta])*
+        #[inline]
+        pub fn $name(vals: &[&[u8]]) -> [u8; HASH_BYTES] {
+            let mut hash_result = core::mem::MaybeUninit::<[u8; HASH_BYTES]>::uninit();
+
+            // The syscall only fails if the input is invalid, which cannot
+            // happen since `vals` is a valid slice.
+            #[cfg(target_os = "solana")]
+            unsafe {
+                crate::syscalls::$syscall_name(
+                    vals as *const _ as *const u8,
+                    vals.len() as u64,
+                    hash_result.as_mut_ptr() as *mut u8,
+                );
+            }
+
+            #[cfg(not(target_os = "solana"))]
+            unsafe {
+                crate::host::$syscall_name(
+                    vals as *const _ as *const u8,
+                    vals.len() as u64,
+                    hash_result.as_mut_ptr() as *mut u8,
+                );
+            }
+
+            // SAFETY: The syscall always writes the hash to `hash_result`.
+            unsafe { hash_result.assume_init() }
+        }
+    };
+}
+
+impl_hash!(
+    /// Returns the SHA-256 hash of the concatenation of `vals`.
+    sha256,
+    sol_sha256
+);
+
+impl_hash!(
+    /// Returns the Keccak-256 hash of the concatenation of `vals`.
+    ///
+    /// This is the original Keccak hash function (as used by Ethereum), not the
+    /// standardized SHA3-256.
+    keccak256,
+    sol_keccak256
+);
+
+impl_hash!(
+    /// Returns the BLAKE3 hash of the concatenation of `vals`.
+    blake3,
+    sol_blake3
+);
+
+/// Collects up to `MAX_SLICES` byte slices and hashes their concatenation with a
+/// single syscall.
+pub struct Hasher<'a, const MAX_SLICES: usize> {
+    /// Hash function to use.
+    hash_fn: fn(&[&[u8]]) -> [u8; HASH_BYTES],
+
+    /// Slices to hash.
+    vals: [&'a [u8]; MAX_SLICES],
+
+    /// Number of slices collected.
+    len: usize,
+}
+
+impl<'a, const MAX_SLICES: usize> Hasher<'a, MAX_SLICES> {
+    /// Creates a new hasher using the given hash function.
+    #[inline]
+    const fn new(hash_fn: fn(&[&[u8]]) -> [u8; HASH_BYTES]) -> Self {
+        Self {
+            hash_fn,
+            vals: [&[]; MAX_SLICES],
+            len: 0,
+        }
+    }
+
+    /// Creates a new SHA-256 hasher.
+    #[inline]
+    pub const fn sha256() -> Self {
+        Self::new(sha256)
+    }
+
+    /// Creates a new Keccak-256 hasher.
+    #[inline]
+    pub const fn keccak256() -> Self {
+        Self::new(keccak256)
+    }
+
+    /// Creates a new BLAKE3 hasher.
+    #[inline]
+    pub const fn blake3() -> Self {
+        Self::new(blake3)
+    }
+
+    /// Adds a slice to the data to hash.
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if the hasher already holds
+    /// `MAX_SLICES` slices.
+    #[inline]
+    pub fn hash(&mut self, val: &'a [u8]) -> Result<(), ProgramError> {
+        if self.len == MAX_SLICES {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.vals[self.len] = val;
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Adds multiple slices to the data to hash.
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] without adding any slice if the
+    /// hasher does not have space for all of them.
+    #[inline]
+    pub fn hashv(&mut self, vals: &[&'a [u8]]) -> Result<(), ProgramError> {
+        if vals.len() > MAX_SLICES - self.len {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.vals[self.len..self.len + vals.len()].copy_from_slice(vals);
+        self.len += vals.len();
+
+        Ok(())
+    }
+
+    /// Returns the hash of the concatenation of the slices added so far.
+    #[inline]
+    pub fn result(&self) -> Hash {
+        Hash((self.hash_fn)(&self.vals[..self.len]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_eq() {
+        let hash = Hash::new([1; HASH_BYTES]);
+        assert_eq!(hash, Hash::from([1; HASH_BYTES]));
+
+        let mut bytes = [1; HASH_BYTES];
+        bytes[HASH_BYTES - 1] = 2;
+        assert_ne!(hash, Hash::new(bytes));
+    }
+
+    #[test]
+    fn test_hasher() {
+        let mut hasher = Hasher::<3>::keccak256();
+        hasher.hash(b"a").unwrap();
+        hasher.hashv(&[b"b", b"c"]).unwrap();
+        assert_eq!(hasher.hash(b"d"), Err(ProgramError::InvalidArgument));
+        assert_eq!(hasher.result(), Hash::new(keccak256(&[b"abc"])));
+
+        let mut hasher = Hasher::<2>::blake3();
+        hasher.hash(b"a").unwrap();
+        assert_eq!(
+            hasher.hashv(&[b"b", b"c"]),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(hasher.result(), Hash::new(blake3(&[b"a"])));
+
+        let hasher = Hasher::<1>::sha256();
+        assert_eq!(hasher.result(), Hash::new(sha256(&[])));
+    }
+}
@@ -0,0 +1,562 @@
+//! Thread-local backend for the host syscalls.
+
+use core::cell::{Cell, RefCell};
+use std::{
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    instruction::ProcessedAccountMeta,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{
+        clock::{Clock, CLOCK_ID},
+        epoch_rewards::{EpochRewards, EPOCH_REWARDS_ID},
+        epoch_schedule::{EpochSchedule, EPOCH_SCHEDULE_ID},
+        fees::Fees,
+        last_restart_slot::{LastRestartSlot, LAST_RESTART_SLOT_ID},
+        rent::{Rent, RENT_ID},
+    },
+};
+
+std::thread_local! {
+    /// Backend installed for the current thread.
+    static BACKEND: RefCell<Option<Rc<dyn Backend>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with the backend installed for the current thread, if any.
+pub(super) fn with<R>(f: impl FnOnce(&dyn Backend) -> R) -> Option<R> {
+    // The backend is cloned so that it can be re-entered from `f`.
+    let backend = BACKEND.with(|backend| backend.borrow().clone());
+    backend.map(|backend| f(backend.as_ref()))
+}
+
+/// Installs `backend` for the current thread.
+///
+/// The previously installed backend (if any) is restored when the returned
+/// guard is dropped.
+#[must_use = "the backend is uninstalled when the guard is dropped"]
+pub fn set_backend(backend: Rc<dyn Backend>) -> BackendGuard {
+    let previous = BACKEND.with(|current| current.borrow_mut().replace(backend));
+    BackendGuard { previous }
+}
+
+/// Guard that restores the previous backend when dropped.
+pub struct BackendGuard {
+    /// Backend installed before the current one.
+    previous: Option<Rc<dyn Backend>>,
+}
+
+impl Drop for BackendGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        BACKEND.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Host implementation of the syscalls.
+///
+/// All methods have a default implementation, so a backend only needs to
+/// implement the functionality required by a test.
+pub trait Backend {
+    /// Print a message to the log.
+    fn log(&self, message: &str) {
+        let _ = message;
+    }
+
+    /// Print 64-bit values represented as hexadecimal to the log.
+    fn log_64(&self, arg1: u64, arg2: u64, arg3: u64, arg4: u64, arg5: u64) {
+        self.log(&format!(
+            "{arg1:#x}, {arg2:#x}, {arg3:#x}, {arg4:#x}, {arg5:#x}"
+        ));
+    }
+
+    /// Print a `Pubkey` to the log.
+    fn log_pubkey(&self, pubkey: &Pubkey) {
+        self.log(&encode_base58(pubkey));
+    }
+
+    /// Print some slices to the log.
+    fn log_data(&self, data: &[&[u8]]) {
+        let _ = data;
+    }
+
+    /// Print the remaining compute units to the log.
+    fn log_compute_units(&self) {
+        self.log(&format!(
+            "Program consumption: {} units remaining",
+            self.remaining_compute_units()
+        ));
+    }
+
+    /// Return the remaining compute units.
+    fn remaining_compute_units(&self) -> u64 {
+        0
+    }
+
+    /// Return the current stack height.
+    ///
+    /// The default value represents a top-level instruction.
+    fn stack_height(&self) -> u64 {
+        1
+    }
+
+    /// Set the return data of the running program.
+    fn set_return_data(&self, data: &[u8]) {
+        let _ = data;
+    }
+
+    /// Return the program id and the data of the most recently set return data.
+    fn get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        None
+    }
+
+    /// Return the active stake delegated to `vote_address` in the current epoch or,
+    /// if `vote_address` is `None`, the total active stake.
+    fn get_epoch_stake(&self, vote_address: Option<&Pubkey>) -> u64 {
+        let _ = vote_address;
+        0
+    }
+
+    /// Return the processed sibling instruction at `index`, where `0` is the most
+    /// recently processed sibling.
+    fn get_processed_sibling_instruction(&self, index: usize) -> Option<SiblingInstruction> {
+        let _ = index;
+        None
+    }
+
+    /// Return whether `point` is a valid point of the Curve25519 representation
+    /// `curve_id`.
+    fn curve_validate_point(&self, curve_id: u64, point: &[u8; 32]) -> bool {
+        let _ = (curve_id, point);
+        false
+    }
+
+    /// Perform the group operation `group_op` of the Curve25519 representation
+    /// `curve_id`, returning the resulting point or `None` if the operation fails.
+    ///
+    /// For scalar multiplication, `left` is the scalar and `right` is the point.
+    fn curve_group_op(
+        &self,
+        curve_id: u64,
+        group_op: u64,
+        left: &[u8; 32],
+        right: &[u8; 32],
+    ) -> Option<[u8; 32]> {
+        let _ = (curve_id, group_op, left, right);
+        None
+    }
+
+    /// Compute the multiscalar multiplication of `scalars` and `points` of the
+    /// Curve25519 representation `curve_id`, returning the resulting point or
+    /// `None` if the operation fails.
+    fn curve_multiscalar_mul(
+        &self,
+        curve_id: u64,
+        scalars: &[[u8; 32]],
+        points: &[[u8; 32]],
+    ) -> Option<[u8; 32]> {
+        let _ = (curve_id, scalars, points);
+        None
+    }
+
+    /// Perform the `alt_bn128` group operation `op` on `input`, returning its
+    /// output or `None` if the operation fails.
+    ///
+    /// The input has the same encoding as the `sol_alt_bn128_group_op` syscall.
+    fn alt_bn128_group_op(&self, op: u64, input: &[u8]) -> Option<Vec<u8>> {
+        let _ = (op, input);
+        None
+    }
+
+    /// Return the account data of the sysvar `id`, used for partial sysvar reads.
+    fn get_sysvar_data(&self, id: &Pubkey) -> Option<Vec<u8>> {
+        let _ = id;
+        None
+    }
+
+    /// Return the `Clock` sysvar.
+    fn get_clock(&self) -> Result<Clock, ProgramError> {
+        Err(ProgramError::UnsupportedSysvar)
+    }
+
+    /// Return the `EpochRewards` sysvar.
+    fn get_epoch_rewards(&self) -> Result<EpochRewards, ProgramError> {
+        Err(ProgramError::UnsupportedSysvar)
+    }
+
+    /// Return the `EpochSchedule` sysvar.
+    fn get_epoch_schedule(&self) -> Result<EpochSchedule, ProgramError> {
+        Err(ProgramError::UnsupportedSysvar)
+    }
+
+    /// Return the `Fees` sysvar.
+    fn get_fees(&self) -> Result<Fees, ProgramError> {
+        Err(ProgramError::UnsupportedSysvar)
+    }
+
+    /// Return the `LastRestartSlot` sysvar.
+    fn get_last_restart_slot(&self) -> Result<LastRestartSlot, ProgramError> {
+        Err(ProgramError::UnsupportedSysvar)
+    }
+
+    /// Return the `Rent` sysvar.
+    fn get_rent(&self) -> Result<Rent, ProgramError> {
+        Err(ProgramError::UnsupportedSysvar)
+    }
+}
+
+/// A processed sibling instruction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SiblingInstruction {
+    /// Program invoked by the instruction.
+    pub program_id: Pubkey,
+
+    /// Accounts of the instruction.
+    pub accounts: Vec<ProcessedAccountMeta>,
+
+    /// Data of the instruction.
+    pub data: Vec<u8>,
+}
+
+/// A [`Backend`] for native tests.
+///
+/// Sysvars that are set to `None` are reported as unsupported. The account data of
+/// sysvars read partially is taken from [`TestBackend::sysvar_data`] or, if not
+/// present there, from the value of the corresponding sysvar. Messages written to
+/// the log are captured and can be inspected with [`TestBackend::logs`].
+pub struct TestBackend {
+    /// Program id of the running program, used to set the return data.
+    pub program_id: Pubkey,
+
+    /// Value of the `Clock` sysvar.
+    pub clock: Option<Clock>,
+
+    /// Value of the `EpochRewards` sysvar.
+    pub epoch_rewards: Option<EpochRewards>,
+
+    /// Value of the `EpochSchedule` sysvar.
+    pub epoch_schedule: Option<EpochSchedule>,
+
+    /// Value of the `LastRestartSlot` sysvar.
+    pub last_restart_slot: Option<LastRestartSlot>,
+
+    /// Value of the `Rent` sysvar.
+    pub rent: Option<Rent>,
+
+    /// Account data of sysvars, used for partial sysvar reads.
+    pub sysvar_data: Vec<(Pubkey, Vec<u8>)>,
+
+    /// Active stake delegated to each vote account in the current epoch.
+    pub epoch_stakes: Vec<(Pubkey, u64)>,
+
+    /// Remaining compute units.
+    pub remaining_compute_units: Cell<u64>,
+
+    /// Current stack height.
+    pub stack_height: Cell<u64>,
+
+    /// Captured log messages.
+    pub logs: RefCell<Vec<String>>,
+
+    /// Captured data logs.
+    pub data_logs: RefCell<Vec<Vec<Vec<u8>>>>,
+
+    /// Current return data.
+    pub return_data: RefCell<Option<(Pubkey, Vec<u8>)>>,
+
+    /// Processed sibling instructions, from the most recently processed one.
+    pub processed_sibling_instructions: Vec<SiblingInstruction>,
+}
+
+impl Default for TestBackend {
+    fn default() -> Self {
+        Self {
+            program_id: Pubkey::default(),
+            clock: None,
+            epoch_rewards: None,
+            epoch_schedule: None,
+            last_restart_slot: None,
+            rent: None,
+            sysvar_data: Vec::new(),
+            epoch_stakes: Vec::new(),
+            remaining_compute_units: Cell::new(DEFAULT_COMPUTE_UNITS),
+            stack_height: Cell::new(1),
+            logs: RefCell::default(),
+            data_logs: RefCell::default(),
+            return_data: RefCell::default(),
+            processed_sibling_instructions: Vec::new(),
+        }
+    }
+}
+
+/// Default compute units available to a test, matching the maximum compute
+/// units of a transaction.
+const DEFAULT_COMPUTE_UNITS: u64 = 1_400_000;
+
+impl TestBackend {
+    /// Returns the captured log messages.
+    pub fn logs(&self) -> Vec<String> {
+        self.logs.borrow().clone()
+    }
+
+    /// Returns the captured data logs.
+    pub fn data_logs(&self) -> Vec<Vec<Vec<u8>>> {
+        self.data_logs.borrow().clone()
+    }
+
+    /// Clears the captured log messages and data logs.
+    pub fn clear_logs(&self) {
+        self.logs.borrow_mut().clear();
+        self.data_logs.borrow_mut().clear();
+    }
+}
+
+impl Backend for TestBackend {
+    fn log(&self, message: &str) {
+        self.logs.borrow_mut().push(message.to_string());
+    }
+
+    fn log_data(&self, data: &[&[u8]]) {
+        self.data_logs
+            .borrow_mut()
+            .push(data.iter().map(|slice| slice.to_vec()).collect());
+    }
+
+    fn remaining_compute_units(&self) -> u64 {
+        self.remaining_compute_units.get()
+    }
+
+    fn stack_height(&self) -> u64 {
+        self.stack_height.get()
+    }
+
+    fn set_return_data(&self, data: &[u8]) {
+        *self.return_data.borrow_mut() = if data.is_empty() {
+            None
+        } else {
+            Some((self.program_id, data.to_vec()))
+        };
+    }
+
+    fn get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.return_data.borrow().clone()
+    }
+
+    fn get_epoch_stake(&self, vote_address: Option<&Pubkey>) -> u64 {
+        match vote_address {
+            Some(vote_address) => self
+                .epoch_stakes
+                .iter()
+                .find(|(key, _)| key == vote_address)
+                .map_or(0, |(_, stake)| *stake),
+            None => self.epoch_stakes.iter().map(|(_, stake)| stake).sum(),
+        }
+    }
+
+    fn get_processed_sibling_instruction(&self, index: usize) -> Option<SiblingInstruction> {
+        self.processed_sibling_instructions.get(index).cloned()
+    }
+
+    fn get_sysvar_data(&self, id: &Pubkey) -> Option<Vec<u8>> {
+        if let Some((_, data)) = self.sysvar_data.iter().find(|(key, _)| key == id) {
+            return Some(data.clone());
+        }
+
+        match *id {
+            CLOCK_ID => self
+                .clock
+                .as_ref()
+                .map(|sysvar| account_data(sysvar, Clock::LEN)),
+            EPOCH_REWARDS_ID => self
+                .epoch_rewards
+                .as_ref()
+                .map(|sysvar| account_data(sysvar, EpochRewards::LEN)),
+            EPOCH_SCHEDULE_ID => self
+                .epoch_schedule
+                .as_ref()
+                .map(|sysvar| account_data(sysvar, EpochSchedule::LEN)),
+            LAST_RESTART_SLOT_ID => self
+                .last_restart_slot
+                .as_ref()
+                .map(|sysvar| account_data(sysvar, LastRestartSlot::LEN)),
+            RENT_ID => self
+                .rent
+                .as_ref()
+                .map(|sysvar| account_data(sysvar, Rent::LEN)),
+            _ => None,
+        }
+    }
+
+    fn get_clock(&self) -> Result<Clock, ProgramError> {
+        self.clock.ok_or(ProgramError::UnsupportedSysvar)
+    }
+
+    fn get_epoch_rewards(&self) -> Result<EpochRewards, ProgramError> {
+        self.epoch_rewards.ok_or(ProgramError::UnsupportedSysvar)
+    }
+
+    fn get_epoch_schedule(&self) -> Result<EpochSchedule, ProgramError> {
+        self.epoch_schedule.ok_or(ProgramError::UnsupportedSysvar)
+    }
+
+    fn get_last_restart_slot(&self) -> Result<LastRestartSlot, ProgramError> {
+        self.last_restart_slot
+            .ok_or(ProgramError::UnsupportedSysvar)
+    }
+
+    fn get_rent(&self) -> Result<Rent, ProgramError> {
+        self.rent.clone().ok_or(ProgramError::UnsupportedSysvar)
+    }
+}
+
+/// Returns the first `len` bytes of `sysvar`, which match the sysvar account data.
+fn account_data<T>(sysvar: &T, len: usize) -> Vec<u8> {
+    debug_assert!(len <= core::mem::size_of::<T>());
+    // SAFETY: The first `len` bytes of the sysvar types do not contain padding.
+    unsafe { core::slice::from_raw_parts(sysvar as *const T as *const u8, len) }.to_vec()
+}
+
+/// Encodes a `Pubkey` as a base58 string, as the runtime does when logging it.
+fn encode_base58(pubkey: &Pubkey) -> String {
+    const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    let mut digits: Vec<u8> = Vec::with_capacity(44);
+
+    for byte in pubkey {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // leading zeros are encoded as '1'
+    let mut encoded = "1".repeat(pubkey.iter().take_while(|byte| **byte == 0).count());
+    encoded.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|digit| ALPHABET[*digit as usize] as char),
+    );
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, vec};
+
+    use super::*;
+    use crate::{
+        cpi::{get_return_data, set_return_data},
+        log::{sol_log_compute_units, sol_log_data},
+        msg,
+        sysvars::{FixedLayoutSysvar, Sysvar},
+    };
+
+    #[test]
+    fn test_sysvars() {
+        assert_eq!(Clock::get().err(), Some(ProgramError::UnsupportedSysvar));
+
+        let backend = Rc::new(TestBackend {
+            clock: Some(Clock {
+                slot: 10,
+                unix_timestamp: 1_000,
+                ..Clock::default()
+            }),
+            epoch_rewards: Some(EpochRewards::new(5, 2, [1u8; 32], 1 << 70, 500, 100, true)),
+            epoch_schedule: Some(EpochSchedule::custom(8192, 4096, true)),
+            last_restart_slot: Some(LastRestartSlot::new(42)),
+            ..TestBackend::default()
+        });
+        let guard = set_backend(backend);
+
+        let clock = Clock::get().unwrap();
+        assert_eq!(clock.slot, 10);
+        assert_eq!(clock.unix_timestamp, 1_000);
+        assert_eq!(
+            EpochSchedule::get().unwrap(),
+            EpochSchedule::custom(8192, 4096, true)
+        );
+        assert_eq!(
+            EpochRewards::get().unwrap(),
+            EpochRewards::new(5, 2, [1u8; 32], 1 << 70, 500, 100, true)
+        );
+        assert_eq!(LastRestartSlot::get().unwrap().last_restart_slot(), 42);
+
+        // partial reads
+        assert_eq!(
+            LastRestartSlot::get_array::<8>(0).unwrap(),
+            42u64.to_le_bytes()
+        );
+        assert_eq!(
+            EpochSchedule::get_array::<8>(25).unwrap(),
+            8160u64.to_le_bytes()
+        );
+        assert_eq!(EpochRewards::get_array::<1>(80).unwrap(), [1]);
+        assert_eq!(
+            EpochRewards::get_array::<2>(80).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            Rent::get_array::<8>(0).err(),
+            Some(ProgramError::UnsupportedSysvar)
+        );
+        assert_eq!(Rent::get().err(), Some(ProgramError::UnsupportedSysvar));
+
+        drop(guard);
+
+        assert_eq!(Clock::get().err(), Some(ProgramError::UnsupportedSysvar));
+    }
+
+    #[test]
+    fn test_logs() {
+        let backend = Rc::new(TestBackend::default());
+        let _guard = set_backend(backend.clone());
+
+        backend.remaining_compute_units.set(5_000);
+
+        msg!("Hello");
+        crate::pubkey::log(&[0u8; 32]);
+        sol_log_compute_units();
+        sol_log_data(&[b"data", &[1, 2]]);
+
+        assert_eq!(
+            backend.logs(),
+            [
+                "Hello",
+                "11111111111111111111111111111111",
+                "Program consumption: 5000 units remaining",
+            ]
+        );
+        assert_eq!(
+            backend.data_logs(),
+            vec![vec![b"data".to_vec(), vec![1, 2]]]
+        );
+    }
+
+    #[test]
+    fn test_return_data() {
+        let backend = Rc::new(TestBackend {
+            program_id: [5u8; 32],
+            ..TestBackend::default()
+        });
+        let _guard = set_backend(backend);
+
+        assert!(get_return_data().is_none());
+
+        set_return_data(&[1, 2, 3]);
+
+        let return_data = get_return_data().unwrap();
+        assert_eq!(return_data.program_id(), &[5u8; 32]);
+        assert_eq!(return_data.as_slice(), &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,175 @@
+//! Modular exponentiation of big integers.
+//!
+//! Integers are represented as little-endian arrays of 64-bit limbs large enough
+//! to hold twice the maximum modulus, so additions and shifts of reduced values
+//! never overflow. Multiplications are performed with a bit-serial interleaved
+//! reduction, which is simple and does not require an odd modulus.
+
+use crate::big_mod_exp::MAX_LEN;
+use core::cmp::Ordering;
+
+/// Number of limbs of an integer, with an extra limb of headroom.
+const LIMBS: usize = MAX_LEN / 8 + 1;
+
+/// A big integer as little-endian 64-bit limbs.
+type Limbs = [u64; LIMBS];
+
+/// Loads a big-endian integer of at most `MAX_LEN` bytes.
+fn from_be_bytes(bytes: &[u8]) -> Limbs {
+    let mut limbs = [0u64; LIMBS];
+    for (i, byte) in bytes.iter().rev().enumerate() {
+        limbs[i / 8] |= (*byte as u64) << ((i % 8) * 8);
+    }
+    limbs
+}
+
+/// Stores the lowest `bytes.len()` bytes of the integer in big-endian order.
+fn to_be_bytes(limbs: &Limbs, bytes: &mut [u8]) {
+    for (i, byte) in bytes.iter_mut().rev().enumerate() {
+        *byte = (limbs[i / 8] >> ((i % 8) * 8)) as u8;
+    }
+}
+
+fn cmp(a: &Limbs, b: &Limbs) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+/// Computes `a - b`, assuming that `a >= b`.
+fn sub_assign(a: &mut Limbs, b: &Limbs) {
+    let mut borrow = false;
+    for (a, b) in a.iter_mut().zip(b.iter()) {
+        let (difference, overflow_1) = a.overflowing_sub(*b);
+        let (difference, overflow_2) = difference.overflowing_sub(borrow as u64);
+        *a = difference;
+        borrow = overflow_1 || overflow_2;
+    }
+}
+
+/// Computes `a + b`, assuming that the result fits in `LIMBS` limbs.
+fn add_assign(a: &mut Limbs, b: &Limbs) {
+    let mut carry = false;
+    for (a, b) in a.iter_mut().zip(b.iter()) {
+        let (sum, overflow_1) = a.overflowing_add(*b);
+        let (sum, overflow_2) = sum.overflowing_add(carry as u64);
+        *a = sum;
+        carry = overflow_1 || overflow_2;
+    }
+}
+
+/// Computes `2 * a`, assuming that the result fits in `LIMBS` limbs.
+fn double_assign(a: &mut Limbs) {
+    let mut carry = 0;
+    for limb in a.iter_mut() {
+        let next = *limb >> 63;
+        *limb = (*limb << 1) | carry;
+        carry = next;
+    }
+}
+
+/// Returns the number of significant bits.
+fn bits(a: &Limbs) -> usize {
+    a.iter()
+        .rposition(|limb| *limb != 0)
+        .map(|i| i * 64 + 64 - a[i].leading_zeros() as usize)
+        .unwrap_or(0)
+}
+
+fn bit(a: &Limbs, index: usize) -> bool {
+    (a[index / 64] >> (index % 64)) & 1 == 1
+}
+
+/// Computes `(a * b) mod m`, assuming that `b < m`.
+fn mul_mod(a: &Limbs, b: &Limbs, m: &Limbs) -> Limbs {
+    let mut result = [0u64; LIMBS];
+
+    for index in (0..bits(a)).rev() {
+        double_assign(&mut result);
+        if cmp(&result, m) != Ordering::Less {
+            sub_assign(&mut result, m);
+        }
+        if bit(a, index) {
+            add_assign(&mut result, b);
+            if cmp(&result, m) != Ordering::Less {
+                sub_assign(&mut result, m);
+            }
+        }
+    }
+
+    result
+}
+
+/// Computes `(base ^ exponent) mod modulus`, writing the result to `result` as a
+/// big-endian integer of `result.len()` bytes.
+///
+/// All inputs must be at most `MAX_LEN` bytes long. The result is zero if the
+/// modulus is zero or one.
+pub(crate) fn big_mod_exp(base: &[u8], exponent: &[u8], modulus: &[u8], result: &mut [u8]) {
+    let modulus = from_be_bytes(modulus);
+
+    if bits(&modulus) <= 1 {
+        result.fill(0);
+        return;
+    }
+
+    let mut one = [0u64; LIMBS];
+    one[0] = 1;
+
+    let base = mul_mod(&from_be_bytes(base), &one, &modulus);
+    let exponent = from_be_bytes(exponent);
+    let mut value = one;
+
+    for index in (0..bits(&exponent)).rev() {
+        value = mul_mod(&value, &value, &modulus);
+        if bit(&exponent, index) {
+            value = mul_mod(&value, &base, &modulus);
+        }
+    }
+
+    to_be_bytes(&value, result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_big_mod_exp() {
+        let mut result = [0u8; 1];
+        big_mod_exp(&[3], &[5], &[7], &mut result);
+        assert_eq!(result, [5]);
+
+        // zero exponent
+        big_mod_exp(&[3], &[], &[7], &mut result);
+        assert_eq!(result, [1]);
+
+        // modulus of one
+        big_mod_exp(&[3], &[5], &[1], &mut result);
+        assert_eq!(result, [0]);
+
+        // base larger than the modulus, with even and odd moduli
+        let mut result = [0u8; 2];
+        big_mod_exp(
+            &[0x12, 0x34, 0x57],
+            &[0x01, 0x01],
+            &[0x10, 0x00],
+            &mut result,
+        );
+        assert_eq!(result, [0x0c, 0x57]);
+        big_mod_exp(
+            &[0x12, 0x34, 0x56],
+            &[0x01, 0x01],
+            &[0x10, 0x03],
+            &mut result,
+        );
+        assert_eq!(result, [0x0d, 0xc3]);
+
+        // 2^4095 mod (2^4096 - 1)
+        let mut base = [0u8; MAX_LEN];
+        base[MAX_LEN - 1] = 2;
+        let modulus = [0xffu8; MAX_LEN];
+        let mut result = [0u8; MAX_LEN];
+        big_mod_exp(&base, &[0x0f, 0xff], &modulus, &mut result);
+        assert_eq!(result[0], 0x80);
+        assert!(result[1..].iter().all(|byte| *byte == 0));
+    }
+}
@@ -0,0 +1,350 @@
+//! BLAKE3 hash function.
+//!
+//! Portable implementation based on the BLAKE3 [reference implementation].
+//!
+//! [reference implementation]: https://github.com/BLAKE3-team/BLAKE3/blob/master/reference_impl/reference_impl.rs
+
+/// Length of a chunk in bytes.
+const CHUNK_LEN: usize = 1024;
+
+/// Length of a block in bytes.
+const BLOCK_LEN: usize = 64;
+
+/// Maximum depth of the chaining value stack, enough for 2^64 bytes of input.
+const MAX_DEPTH: usize = 54;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+
+/// Initialization vector.
+const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// Permutation of the message words applied after each round.
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+/// The mixing function.
+#[inline(always)]
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, mx: u32, my: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(mx);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(my);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+fn round(state: &mut [u32; 16], m: &[u32; 16]) {
+    // mix the columns
+    g(state, 0, 4, 8, 12, m[0], m[1]);
+    g(state, 1, 5, 9, 13, m[2], m[3]);
+    g(state, 2, 6, 10, 14, m[4], m[5]);
+    g(state, 3, 7, 11, 15, m[6], m[7]);
+    // mix the diagonals
+    g(state, 0, 5, 10, 15, m[8], m[9]);
+    g(state, 1, 6, 11, 12, m[10], m[11]);
+    g(state, 2, 7, 8, 13, m[12], m[13]);
+    g(state, 3, 4, 9, 14, m[14], m[15]);
+}
+
+fn compress(
+    chaining_value: &[u32; 8],
+    block_words: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut state = [
+        chaining_value[0],
+        chaining_value[1],
+        chaining_value[2],
+        chaining_value[3],
+        chaining_value[4],
+        chaining_value[5],
+        chaining_value[6],
+        chaining_value[7],
+        IV[0],
+        IV[1],
+        IV[2],
+        IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        block_len,
+        flags,
+    ];
+    let mut block = *block_words;
+
+    for i in 0..7 {
+        round(&mut state, &block);
+        if i < 6 {
+            let mut permuted = [0; 16];
+            for (word, index) in permuted.iter_mut().zip(MSG_PERMUTATION) {
+                *word = block[index];
+            }
+            block = permuted;
+        }
+    }
+
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= chaining_value[i];
+    }
+
+    state
+}
+
+fn first_8_words(compression_output: [u32; 16]) -> [u32; 8] {
+    compression_output[0..8].try_into().unwrap()
+}
+
+fn words_from_le_bytes(bytes: &[u8; BLOCK_LEN]) -> [u32; 16] {
+    let mut words = [0u32; 16];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    words
+}
+
+/// The input to a compression that may be either a chaining value or the root output.
+struct Output {
+    input_chaining_value: [u32; 8],
+    block_words: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        first_8_words(compress(
+            &self.input_chaining_value,
+            &self.block_words,
+            self.counter,
+            self.block_len,
+            self.flags,
+        ))
+    }
+
+    fn root_hash(&self) -> [u8; 32] {
+        let words = compress(
+            &self.input_chaining_value,
+            &self.block_words,
+            0,
+            self.block_len,
+            self.flags | ROOT,
+        );
+
+        let mut hash = [0u8; 32];
+        for (chunk, word) in hash.chunks_exact_mut(4).zip(words.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        hash
+    }
+}
+
+struct ChunkState {
+    chaining_value: [u32; 8],
+    chunk_counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+}
+
+impl ChunkState {
+    fn new(chunk_counter: u64) -> Self {
+        Self {
+            chaining_value: IV,
+            chunk_counter,
+            block: [0; BLOCK_LEN],
+            block_len: 0,
+            blocks_compressed: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed + self.block_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 {
+            CHUNK_START
+        } else {
+            0
+        }
+    }
+
+    fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // if the block buffer is full, compress it and clear it
+            if self.block_len == BLOCK_LEN {
+                self.chaining_value = first_8_words(compress(
+                    &self.chaining_value,
+                    &words_from_le_bytes(&self.block),
+                    self.chunk_counter,
+                    BLOCK_LEN as u32,
+                    self.start_flag(),
+                ));
+                self.blocks_compressed += 1;
+                self.block = [0; BLOCK_LEN];
+                self.block_len = 0;
+            }
+
+            let take = core::cmp::min(BLOCK_LEN - self.block_len, input.len());
+            self.block[self.block_len..self.block_len + take].copy_from_slice(&input[..take]);
+            self.block_len += take;
+            input = &input[take..];
+        }
+    }
+
+    fn output(&self) -> Output {
+        Output {
+            input_chaining_value: self.chaining_value,
+            block_words: words_from_le_bytes(&self.block),
+            counter: self.chunk_counter,
+            block_len: self.block_len as u32,
+            flags: self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+fn parent_output(left_child_cv: [u32; 8], right_child_cv: [u32; 8]) -> Output {
+    let mut block_words = [0; 16];
+    block_words[..8].copy_from_slice(&left_child_cv);
+    block_words[8..].copy_from_slice(&right_child_cv);
+    Output {
+        input_chaining_value: IV,
+        block_words,
+        counter: 0,
+        block_len: BLOCK_LEN as u32,
+        flags: PARENT,
+    }
+}
+
+/// Incremental BLAKE3 hasher.
+struct Hasher {
+    chunk_state: ChunkState,
+    cv_stack: [[u32; 8]; MAX_DEPTH],
+    cv_stack_len: usize,
+}
+
+impl Hasher {
+    fn new() -> Self {
+        Self {
+            chunk_state: ChunkState::new(0),
+            cv_stack: [[0; 8]; MAX_DEPTH],
+            cv_stack_len: 0,
+        }
+    }
+
+    fn push_stack(&mut self, cv: [u32; 8]) {
+        self.cv_stack[self.cv_stack_len] = cv;
+        self.cv_stack_len += 1;
+    }
+
+    fn pop_stack(&mut self) -> [u32; 8] {
+        self.cv_stack_len -= 1;
+        self.cv_stack[self.cv_stack_len]
+    }
+
+    /// Merges completed subtrees, as indicated by the trailing zero bits of
+    /// `total_chunks`, and pushes the new chaining value.
+    fn add_chunk_chaining_value(&mut self, mut new_cv: [u32; 8], mut total_chunks: u64) {
+        while total_chunks & 1 == 0 {
+            new_cv = parent_output(self.pop_stack(), new_cv).chaining_value();
+            total_chunks >>= 1;
+        }
+        self.push_stack(new_cv);
+    }
+
+    fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // if the current chunk is complete, finalize it and start a new one
+            if self.chunk_state.len() == CHUNK_LEN {
+                let chunk_cv = self.chunk_state.output().chaining_value();
+                let total_chunks = self.chunk_state.chunk_counter + 1;
+                self.add_chunk_chaining_value(chunk_cv, total_chunks);
+                self.chunk_state = ChunkState::new(total_chunks);
+            }
+
+            let take = core::cmp::min(CHUNK_LEN - self.chunk_state.len(), input.len());
+            self.chunk_state.update(&input[..take]);
+            input = &input[take..];
+        }
+    }
+
+    fn finalize(&self) -> [u8; 32] {
+        let mut output = self.chunk_state.output();
+        let mut parent_nodes_remaining = self.cv_stack_len;
+
+        while parent_nodes_remaining > 0 {
+            parent_nodes_remaining -= 1;
+            output = parent_output(
+                self.cv_stack[parent_nodes_remaining],
+                output.chaining_value(),
+            );
+        }
+
+        output.root_hash()
+    }
+}
+
+/// Returns the BLAKE3 hash of the concatenation of `vals`.
+pub(crate) fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    for val in vals {
+        hasher.update(val);
+    }
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blake3() {
+        assert_eq!(
+            hashv(&[]),
+            [
+                0xaf, 0x13, 0x49, 0xb9, 0xf5, 0xf9, 0xa1, 0xa6, 0xa0, 0x40, 0x4d, 0xea, 0x36, 0xdc,
+                0xc9, 0x49, 0x9b, 0xcb, 0x25, 0xc9, 0xad, 0xc1, 0x12, 0xb7, 0xcc, 0x9a, 0x93, 0xca,
+                0xe4, 0x1f, 0x32, 0x62
+            ]
+        );
+        assert_eq!(
+            hashv(&[b"ab", b"c"]),
+            [
+                0x64, 0x37, 0xb3, 0xac, 0x38, 0x46, 0x51, 0x33, 0xff, 0xb6, 0x3b, 0x75, 0x27, 0x3a,
+                0x8d, 0xb5, 0x48, 0xc5, 0x58, 0x46, 0x5d, 0x79, 0xdb, 0x03, 0xfd, 0x35, 0x9c, 0x6c,
+                0xd5, 0xbd, 0x9d, 0x85
+            ]
+        );
+        assert_eq!(
+            hashv(&[&[0x61u8; 1_000]]),
+            [
+                0x99, 0x57, 0xa9, 0x01, 0x47, 0x33, 0xdd, 0x6b, 0x6e, 0x2f, 0x6a, 0xbc, 0xbe, 0x7b,
+                0x25, 0x9a, 0x6d, 0xa1, 0xaa, 0x0b, 0x0e, 0x18, 0x4c, 0xd7, 0xbf, 0x18, 0x10, 0xe5,
+                0xc4, 0x25, 0xf4, 0x05
+            ]
+        );
+        // multi-chunk message split at arbitrary boundaries
+        let message = core::array::from_fn::<u8, 5_000, _>(|i| (i % 251) as u8);
+        assert_eq!(
+            hashv(&[&message[..3], &message[3..1025], &message[1025..]]),
+            hashv(&[&message])
+        );
+        assert_eq!(
+            hashv(&[&message]),
+            [
+                0xee, 0x78, 0xd9, 0x20, 0x70, 0xde, 0x3d, 0xf1, 0xc5, 0x7c, 0x37, 0x00, 0x2a, 0xbf,
+                0x0a, 0x6b, 0x1a, 0x65, 0x89, 0xac, 0xde, 0xef, 0x4d, 0x8f, 0xfa, 0xc7, 0xcf, 0x3d,
+                0x9e, 0x8f, 0x28, 0x36
+            ]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Ed25519 curve point validation.
+//!
+//! Field arithmetic over `GF(2^255 - 19)` using five 51-bit limbs, following
+//! the representation used by `curve25519-dalek`.
+
+/// Mask for the lower 51 bits of a limb.
+const LOW_51_BIT_MASK: u64 = (1 << 51) - 1;
+
+/// An element of the field `GF(2^255 - 19)`.
+#[derive(Clone, Copy)]
+struct FieldElement([u64; 5]);
+
+impl FieldElement {
+    /// The field element `1`.
+    const ONE: Self = Self([1, 0, 0, 0, 0]);
+
+    /// Edwards `d` curve parameter, equal to `-121665/121666`.
+    const EDWARDS_D: Self = Self([
+        929955233495203,
+        466365720129213,
+        1662059464998953,
+        2033849074728123,
+        1442794654840575,
+    ]);
+
+    /// Loads a field element from its little-endian byte representation, ignoring
+    /// the most significant bit.
+    fn from_bytes(bytes: &[u8; 32]) -> Self {
+        let load = |i: usize| u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+
+        Self([
+            load(0) & LOW_51_BIT_MASK,
+            (load(6) >> 3) & LOW_51_BIT_MASK,
+            (load(12) >> 6) & LOW_51_BIT_MASK,
+            (load(19) >> 1) & LOW_51_BIT_MASK,
+            (load(24) >> 12) & LOW_51_BIT_MASK,
+        ])
+    }
+
+    /// Returns the canonical little-endian byte representation.
+    fn to_bytes(self) -> [u8; 32] {
+        let mut limbs = Self::reduce(self.0).0;
+
+        // compute `q` such that `limbs - q * p` is the canonical representative
+        let mut q = (limbs[0] + 19) >> 51;
+        q = (limbs[1] + q) >> 51;
+        q = (limbs[2] + q) >> 51;
+        q = (limbs[3] + q) >> 51;
+        q = (limbs[4] + q) >> 51;
+
+        limbs[0] += 19 * q;
+
+        limbs[1] += limbs[0] >> 51;
+        limbs[0] &= LOW_51_BIT_MASK;
+        limbs[2] += limbs[1] >> 51;
+        limbs[1] &= LOW_51_BIT_MASK;
+        limbs[3] += limbs[2] >> 51;
+        limbs[2] &= LOW_51_BIT_MASK;
+        limbs[4] += limbs[3] >> 51;
+        limbs[3] &= LOW_51_BIT_MASK;
+        limbs[4] &= LOW_51_BIT_MASK;
+
+        let mut bytes = [0u8; 32];
+        let mut acc: u128 = 0;
+        let mut bits = 0;
+        let mut index = 0;
+
+        for limb in limbs {
+            acc |= (limb as u128) << bits;
+            bits += 51;
+            while bits >= 8 && index < 32 {
+                bytes[index] = acc as u8;
+                acc >>= 8;
+                bits -= 8;
+                index += 1;
+            }
+        }
+        if index < 32 {
+            bytes[index] = acc as u8;
+        }
+
+        bytes
+    }
+
+    /// Carries the limbs so that each one fits in 51 bits (plus a small excess
+    /// in the first limb).
+    fn reduce(mut limbs: [u64; 5]) -> Self {
+        let c0 = limbs[0] >> 51;
+        let c1 = limbs[1] >> 51;
+        let c2 = limbs[2] >> 51;
+        let c3 = limbs[3] >> 51;
+        let c4 = limbs[4] >> 51;
+
+        limbs[0] &= LOW_51_BIT_MASK;
+        limbs[1] &= LOW_51_BIT_MASK;
+        limbs[2] &= LOW_51_BIT_MASK;
+        limbs[3] &= LOW_51_BIT_MASK;
+        limbs[4] &= LOW_51_BIT_MASK;
+
+        limbs[0] += c4 * 19;
+        limbs[1] += c0;
+        limbs[2] += c1;
+        limbs[3] += c2;
+        limbs[4] += c3;
+
+        Self(limbs)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut limbs = self.0;
+        for (limb, other) in limbs.iter_mut().zip(other.0) {
+            *limb += other;
+        }
+        Self::reduce(limbs)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        // add a multiple of `p` to avoid underflow
+        Self::reduce([
+            (self.0[0] + 36028797018963664) - other.0[0],
+            (self.0[1] + 36028797018963952) - other.0[1],
+            (self.0[2] + 36028797018963952) - other.0[2],
+            (self.0[3] + 36028797018963952) - other.0[3],
+            (self.0[4] + 36028797018963952) - other.0[4],
+        ])
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let m = |x: u64, y: u64| (x as u128) * (y as u128);
+
+        let a = &self.0;
+        let b = &other.0;
+
+        let b1_19 = b[1] * 19;
+        let b2_19 = b[2] * 19;
+        let b3_19 = b[3] * 19;
+        let b4_19 = b[4] * 19;
+
+        let c0 = m(a[0], b[0]) + m(a[4], b1_19) + m(a[3], b2_19) + m(a[2], b3_19) + m(a[1], b4_19);
+        let mut c1 =
+            m(a[1], b[0]) + m(a[0], b[1]) + m(a[4], b2_19) + m(a[3], b3_19) + m(a[2], b4_19);
+        let mut c2 =
+            m(a[2], b[0]) + m(a[1], b[1]) + m(a[0], b[2]) + m(a[4], b3_19) + m(a[3], b4_19);
+        let mut c3 = m(a[3], b[0]) + m(a[2], b[1]) + m(a[1], b[2]) + m(a[0], b[3]) + m(a[4], b4_19);
+        let mut c4 = m(a[4], b[0]) + m(a[3], b[1]) + m(a[2], b[2]) + m(a[1], b[3]) + m(a[0], b[4]);
+
+        c1 += c0 >> 51;
+        c2 += c1 >> 51;
+        c3 += c2 >> 51;
+        c4 += c3 >> 51;
+
+        let mut limbs = [
+            (c0 as u64) & LOW_51_BIT_MASK,
+            (c1 as u64) & LOW_51_BIT_MASK,
+            (c2 as u64) & LOW_51_BIT_MASK,
+            (c3 as u64) & LOW_51_BIT_MASK,
+            (c4 as u64) & LOW_51_BIT_MASK,
+        ];
+
+        limbs[0] += ((c4 >> 51) as u64) * 19;
+        limbs[1] += limbs[0] >> 51;
+        limbs[0] &= LOW_51_BIT_MASK;
+
+        Self(limbs)
+    }
+
+    fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// Raises the element to the power `exponent`, given in little-endian bytes.
+    fn pow(&self, exponent: &[u8; 32]) -> Self {
+        let mut result = Self::ONE;
+
+        for byte in exponent.iter().rev() {
+            for bit in (0..8).rev() {
+                result = result.square();
+                if (byte >> bit) & 1 == 1 {
+                    result = result.mul(self);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Exponent `(p - 1) / 2` used to compute the Legendre symbol.
+const LEGENDRE_EXPONENT: [u8; 32] = [
+    0xf6, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x3f,
+];
+
+/// Canonical representation of `p - 1`.
+const MINUS_ONE: [u8; 32] = [
+    0xec, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f,
+];
+
+/// Checks whether the given bytes represent a (compressed) point on the Ed25519 curve.
+///
+/// This has the same semantics as `CompressedEdwardsY::decompress` of `curve25519-dalek`:
+/// the `y` coordinate is decoded from the lower 255 bits and the point is valid if there
+/// exists an `x` coordinate such that `x^2 = (y^2 - 1) / (d * y^2 + 1)`.
+pub(crate) fn is_on_curve(bytes: &[u8; 32]) -> bool {
+    let y = FieldElement::from_bytes(bytes);
+    let yy = y.square();
+
+    let u = yy.sub(&FieldElement::ONE);
+    let v = FieldElement::EDWARDS_D.mul(&yy).add(&FieldElement::ONE);
+
+    // `v` is never zero, so `u / v` is a square iff the Legendre symbol of
+    // `u * v` is not `-1`.
+    u.mul(&v).pow(&LEGENDRE_EXPONENT).to_bytes() != MINUS_ONE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edwards_d() {
+        // d * 121666 == -121665
+        let d = FieldElement::EDWARDS_D.mul(&FieldElement([121666, 0, 0, 0, 0]));
+        let zero = FieldElement([0; 5]);
+        assert_eq!(
+            d.to_bytes(),
+            zero.sub(&FieldElement([121665, 0, 0, 0, 0])).to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_is_on_curve() {
+        // identity point (y = 1)
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        assert!(is_on_curve(&identity));
+
+        // Ed25519 base point
+        let mut base = [0x66u8; 32];
+        base[0] = 0x58;
+        assert!(is_on_curve(&base));
+
+        // y = 2 is not on the curve
+        let mut point = [0u8; 32];
+        point[0] = 2;
+        assert!(!is_on_curve(&point));
+    }
+}
@@ -0,0 +1,166 @@
+//! Keccak-256 hash function.
+//!
+//! This is the original Keccak submission (as used by Ethereum and the
+//! `sol_keccak256` syscall), which differs from the standardized SHA3-256
+//! in the padding rule.
+
+/// Rate of the sponge in bytes.
+const RATE: usize = 136;
+
+/// Round constants of the Keccak-f[1600] permutation.
+const RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Rotation offsets of the rho step.
+const ROTATIONS: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// Lane positions of the pi step.
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Keccak-f[1600] permutation.
+fn keccak_f(state: &mut [u64; 25]) {
+    for rc in RC {
+        // theta
+        let mut c = [0u64; 5];
+        for (x, column) in c.iter_mut().enumerate() {
+            *column = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+
+        // rho and pi
+        let mut current = state[1];
+        for (position, rotation) in PI.iter().zip(ROTATIONS) {
+            let next = state[*position];
+            state[*position] = current.rotate_left(rotation);
+            current = next;
+        }
+
+        // chi
+        for y in 0..5 {
+            let row = [
+                state[5 * y],
+                state[5 * y + 1],
+                state[5 * y + 2],
+                state[5 * y + 3],
+                state[5 * y + 4],
+            ];
+            for x in 0..5 {
+                state[5 * y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // iota
+        state[0] ^= rc;
+    }
+}
+
+/// XORs a block of `RATE` bytes into the state.
+fn absorb(state: &mut [u64; 25], block: &[u8; RATE]) {
+    for (lane, chunk) in state.iter_mut().zip(block.chunks_exact(8)) {
+        *lane ^= u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    keccak_f(state);
+}
+
+/// Returns the Keccak-256 hash of the concatenation of `vals`.
+pub(crate) fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    let mut state = [0u64; 25];
+    let mut block = [0u8; RATE];
+    let mut block_len = 0;
+
+    for val in vals {
+        for byte in val.iter() {
+            block[block_len] = *byte;
+            block_len += 1;
+
+            if block_len == RATE {
+                absorb(&mut state, &block);
+                block_len = 0;
+            }
+        }
+    }
+
+    // padding
+    block[block_len..].fill(0);
+    block[block_len] ^= 0x01;
+    block[RATE - 1] ^= 0x80;
+    absorb(&mut state, &block);
+
+    let mut hash = [0u8; 32];
+    for (chunk, lane) in hash.chunks_exact_mut(8).zip(state.iter()) {
+        chunk.copy_from_slice(&lane.to_le_bytes());
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            hashv(&[]),
+            [
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+                0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+                0x5d, 0x85, 0xa4, 0x70
+            ]
+        );
+        assert_eq!(
+            hashv(&[b"ab", b"c"]),
+            [
+                0x4e, 0x03, 0x65, 0x7a, 0xea, 0x45, 0xa9, 0x4f, 0xc7, 0xd4, 0x7b, 0xa8, 0x26, 0xc8,
+                0xd6, 0x67, 0xc0, 0xd1, 0xe6, 0xe3, 0x3a, 0x64, 0xa0, 0x36, 0xec, 0x44, 0xf5, 0x8f,
+                0xa1, 0x2d, 0x6c, 0x45
+            ]
+        );
+        // multi-block message split at arbitrary boundaries
+        let message = [0x61u8; 1_000];
+        assert_eq!(
+            hashv(&[&message[..3], &message[3..136], &message[136..]]),
+            hashv(&[&message])
+        );
+        assert_eq!(
+            hashv(&[&message]),
+            [
+                0xb6, 0xa4, 0xac, 0x1f, 0x51, 0x88, 0x4d, 0x71, 0xf3, 0x0f, 0xa3, 0x97, 0xa5, 0xe1,
+                0x55, 0xde, 0x30, 0x99, 0xe1, 0x1f, 0xc0, 0xed, 0xef, 0x5d, 0x08, 0xb6, 0x46, 0xe6,
+                0x21, 0xe1, 0x9d, 0xe9
+            ]
+        );
+    }
+}
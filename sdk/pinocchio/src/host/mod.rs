@@ -0,0 +1,773 @@
+//! Host implementation of the syscalls.
+//!
+//! When a program is not compiled for the `solana` target, the syscall wrappers of
+//! this crate (sysvar [`get`](crate::sysvars::Sysvar::get) and partial
+//! [reads](crate::sysvars::get_sysvar_bytes), logging, return data, processed sibling
+//! instructions, epoch stake and compute units)
+//! are routed to the functions in this module. These functions mirror
+//! the signature of the corresponding syscalls.
+//!
+//! Program derived addresses are computed using a pure Rust implementation of the
+//! runtime algorithm, so [`create_program_address`](crate::pubkey::create_program_address)
+//! and [`find_program_address`](crate::pubkey::find_program_address) return the same
+//! results as their syscall counterparts. The same applies to the [`hash`](crate::hash)
+//! functions, which use pure Rust implementations of SHA-256, Keccak-256 and BLAKE3,
+//! and to [`big_mod_exp`](crate::big_mod_exp::big_mod_exp). Elliptic curve operations
+//! and the Poseidon hash are not emulated: the corresponding functions always fail,
+//! except for the [`curve25519`](crate::curve25519) and [`alt_bn128`](crate::alt_bn128)
+//! group operations, which a [`Backend`] can implement.
+//!
+//! With the `std` feature enabled, the calls are dispatched to a [`Backend`] installed
+//! for the current thread using [`set_backend`]. This allows native tests to provide
+//! sysvar values, compute units and stack height, as well as to capture the program
+//! logs. Without the `std` feature &mdash; or when no backend is installed &mdash; logs
+//! are discarded and sysvars are not available.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use std::rc::Rc;
+//! use pinocchio::{
+//!     host::{set_backend, TestBackend},
+//!     msg,
+//!     sysvars::{clock::Clock, Sysvar},
+//! };
+//!
+//! let backend = Rc::new(TestBackend {
+//!     clock: Some(Clock {
+//!         slot: 42,
+//!         ..Clock::default()
+//!     }),
+//!     ..TestBackend::default()
+//! });
+//! let _guard = set_backend(backend.clone());
+//!
+//! assert_eq!(Clock::get().unwrap().slot, 42);
+//!
+//! msg!("Hello from my program!");
+//! assert_eq!(backend.logs(), ["Hello from my program!"]);
+//! # }
+//! ```
+
+use crate::{
+    big_mod_exp::{BigModExpParams, MAX_LEN},
+    instruction::{AccountMeta, ProcessedSiblingInstruction},
+    program_error::ProgramError,
+    pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN, PDA_MARKER},
+};
+
+mod big_mod_exp;
+mod blake3;
+mod edwards;
+mod keccak;
+mod sha256;
+
+#[cfg(feature = "std")]
+pub use backend::*;
+
+/// Print a string to the log.
+///
+/// # Safety
+///
+/// `message` must point to `len` valid UTF-8 bytes.
+#[inline(always)]
+pub unsafe fn sol_log_(message: *const u8, len: u64) {
+    #[cfg(feature = "std")]
+    backend::with(|backend| {
+        backend.log(core::str::from_utf8_unchecked(core::slice::from_raw_parts(
+            message,
+            len as usize,
+        )))
+    });
+
+    #[cfg(not(feature = "std"))]
+    core::hint::black_box((message, len));
+}
+
+/// Print 64-bit values represented as hexadecimal to the log.
+#[inline(always)]
+pub fn sol_log_64_(arg1: u64, arg2: u64, arg3: u64, arg4: u64, arg5: u64) {
+    #[cfg(feature = "std")]
+    backend::with(|backend| backend.log_64(arg1, arg2, arg3, arg4, arg5));
+
+    #[cfg(not(feature = "std"))]
+    core::hint::black_box((arg1, arg2, arg3, arg4, arg5));
+}
+
+/// Print the remaining compute units to the log.
+#[inline(always)]
+pub fn sol_log_compute_units_() {
+    #[cfg(feature = "std")]
+    backend::with(|backend| backend.log_compute_units());
+}
+
+/// Print a `Pubkey` to the log.
+///
+/// # Safety
+///
+/// `pubkey_addr` must point to a valid `Pubkey`.
+#[inline(always)]
+pub unsafe fn sol_log_pubkey(pubkey_addr: *const u8) {
+    #[cfg(feature = "std")]
+    backend::with(|backend| backend.log_pubkey(&*(pubkey_addr as *const Pubkey)));
+
+    #[cfg(not(feature = "std"))]
+    core::hint::black_box(pubkey_addr);
+}
+
+/// Print some slices to the log.
+///
+/// # Safety
+///
+/// `data` must point to `data_len` valid `&[u8]` slices.
+#[inline(always)]
+pub unsafe fn sol_log_data(data: *const u8, data_len: u64) {
+    #[cfg(feature = "std")]
+    backend::with(|backend| {
+        backend.log_data(core::slice::from_raw_parts(
+            data as *const &[u8],
+            data_len as usize,
+        ))
+    });
+
+    #[cfg(not(feature = "std"))]
+    core::hint::black_box((data, data_len));
+}
+
+/// Set the return data of the running program.
+///
+/// # Safety
+///
+/// `data` must point to `length` valid bytes.
+#[inline(always)]
+pub unsafe fn sol_set_return_data(data: *const u8, length: u64) {
+    #[cfg(feature = "std")]
+    backend::with(|backend| {
+        backend.set_return_data(core::slice::from_raw_parts(data, length as usize))
+    });
+
+    #[cfg(not(feature = "std"))]
+    core::hint::black_box((data, length));
+}
+
+/// Get the return data, returning its full length.
+///
+/// At most `length` bytes are copied to `data`.
+///
+/// # Safety
+///
+/// `data` must be valid for writes of `length` bytes and `program_id` must be valid
+/// for writes.
+#[inline(always)]
+pub unsafe fn sol_get_return_data(data: *mut u8, length: u64, program_id: *mut Pubkey) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        match backend::with(|backend| backend.get_return_data()).flatten() {
+            Some((id, return_data)) => {
+                let copied = core::cmp::min(return_data.len(), length as usize);
+                core::ptr::copy_nonoverlapping(return_data.as_ptr(), data, copied);
+                *program_id = id;
+                return_data.len() as u64
+            }
+            None => 0,
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        core::hint::black_box((data, length, program_id));
+        0
+    }
+}
+
+/// Return the remaining compute units.
+#[inline(always)]
+pub fn sol_remaining_compute_units() -> u64 {
+    #[cfg(feature = "std")]
+    {
+        backend::with(|backend| backend.remaining_compute_units()).unwrap_or_default()
+    }
+
+    #[cfg(not(feature = "std"))]
+    0
+}
+
+/// Return the current stack height.
+#[inline(always)]
+pub fn sol_get_stack_height() -> u64 {
+    #[cfg(feature = "std")]
+    {
+        backend::with(|backend| backend.stack_height()).unwrap_or(1)
+    }
+
+    #[cfg(not(feature = "std"))]
+    1
+}
+
+/// Return the active stake delegated to the vote account at `vote_address` in the
+/// current epoch or, if `vote_address` is null, the total active stake.
+///
+/// # Safety
+///
+/// `vote_address` must be null or point to a valid `Pubkey`.
+#[inline(always)]
+pub unsafe fn sol_get_epoch_stake(vote_address: *const u8) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        let vote_address = (vote_address as *const Pubkey).as_ref();
+        backend::with(|backend| backend.get_epoch_stake(vote_address)).unwrap_or_default()
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        core::hint::black_box(vote_address);
+        0
+    }
+}
+
+/// Get a processed sibling instruction.
+///
+/// The instruction is only copied when the lengths in `meta` match the lengths of
+/// the instruction. The lengths of the instruction are always written to `meta`.
+/// Returns `1` if the instruction was found and `0` otherwise.
+///
+/// # Safety
+///
+/// `meta` and `program_id` must be valid for reads and writes, and `data` and
+/// `accounts` must be valid for writes of the lengths in `meta`. `accounts` must
+/// point to [`ProcessedAccountMeta`](crate::instruction::ProcessedAccountMeta)
+/// values.
+#[inline(always)]
+pub unsafe fn sol_get_processed_sibling_instruction(
+    index: u64,
+    meta: *mut ProcessedSiblingInstruction,
+    program_id: *mut Pubkey,
+    data: *mut u8,
+    accounts: *mut AccountMeta,
+) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        let sibling =
+            backend::with(|backend| backend.get_processed_sibling_instruction(index as usize))
+                .flatten();
+
+        match sibling {
+            Some(sibling) => {
+                let meta = &mut *meta;
+
+                if meta.data_len == sibling.data.len() as u64
+                    && meta.accounts_len == sibling.accounts.len() as u64
+                {
+                    *program_id = sibling.program_id;
+                    core::ptr::copy_nonoverlapping(sibling.data.as_ptr(), data, sibling.data.len());
+                    core::ptr::copy_nonoverlapping(
+                        sibling.accounts.as_ptr(),
+                        accounts as *mut crate::instruction::ProcessedAccountMeta,
+                        sibling.accounts.len(),
+                    );
+                }
+
+                meta.data_len = sibling.data.len() as u64;
+                meta.accounts_len = sibling.accounts.len() as u64;
+                1
+            }
+            None => 0,
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        core::hint::black_box((index, meta, program_id, data, accounts));
+        0
+    }
+}
+
+/// Create a program derived address.
+///
+/// Returns [`MAX_SEED_LENGTH_EXCEEDED`](crate::program_error::MAX_SEED_LENGTH_EXCEEDED)
+/// if there are too many seeds or a seed is too long, and
+/// [`INVALID_SEEDS`](crate::program_error::INVALID_SEEDS) if the resulting address
+/// lies on the Ed25519 curve.
+///
+/// # Safety
+///
+/// `seeds_addr` must point to `seeds_len` valid `&[u8]` slices, `program_id_addr`
+/// must point to a valid `Pubkey` and `address_bytes_addr` must be valid for writes
+/// of a `Pubkey`.
+pub unsafe fn sol_create_program_address(
+    seeds_addr: *const u8,
+    seeds_len: u64,
+    program_id_addr: *const u8,
+    address_bytes_addr: *mut u8,
+) -> u64 {
+    let seeds = core::slice::from_raw_parts(seeds_addr as *const &[u8], seeds_len as usize);
+    let program_id = &*(program_id_addr as *const Pubkey);
+
+    match derive_address(seeds, &[], program_id) {
+        Ok(address) => {
+            core::ptr::write(address_bytes_addr as *mut Pubkey, address);
+            crate::SUCCESS
+        }
+        Err(error) => error.into(),
+    }
+}
+
+/// Find a valid program derived address and its corresponding bump seed.
+///
+/// Returns [`INVALID_SEEDS`](crate::program_error::INVALID_SEEDS) if no bump seed
+/// results in a valid address.
+///
+/// # Safety
+///
+/// `seeds_addr` must point to `seeds_len` valid `&[u8]` slices, `program_id_addr`
+/// must point to a valid `Pubkey`, `address_bytes_addr` must be valid for writes
+/// of a `Pubkey` and `bump_seed_addr` must be valid for writes of a `u8`.
+pub unsafe fn sol_try_find_program_address(
+    seeds_addr: *const u8,
+    seeds_len: u64,
+    program_id_addr: *const u8,
+    address_bytes_addr: *mut u8,
+    bump_seed_addr: *mut u8,
+) -> u64 {
+    let seeds = core::slice::from_raw_parts(seeds_addr as *const &[u8], seeds_len as usize);
+    let program_id = &*(program_id_addr as *const Pubkey);
+
+    for bump in (1..=u8::MAX).rev() {
+        match derive_address(seeds, &[bump], program_id) {
+            Ok(address) => {
+                core::ptr::write(address_bytes_addr as *mut Pubkey, address);
+                *bump_seed_addr = bump;
+                return crate::SUCCESS;
+            }
+            Err(ProgramError::InvalidSeeds) => continue,
+            Err(error) => return error.into(),
+        }
+    }
+
+    crate::program_error::INVALID_SEEDS
+}
+
+/// Derives a program address from the `seeds`, an optional `bump` seed and the
+/// `program_id`.
+fn derive_address(
+    seeds: &[&[u8]],
+    bump: &[u8],
+    program_id: &Pubkey,
+) -> Result<Pubkey, ProgramError> {
+    if seeds.len() + bump.len() > MAX_SEEDS {
+        return Err(ProgramError::MaxSeedLengthExceeded);
+    }
+    if seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+        return Err(ProgramError::MaxSeedLengthExceeded);
+    }
+
+    let mut hasher = sha256::Sha256::new();
+    for seed in seeds {
+        hasher.update(seed);
+    }
+    hasher.update(bump);
+    hasher.update(program_id);
+    hasher.update(PDA_MARKER);
+
+    let address = hasher.finalize();
+
+    if edwards::is_on_curve(&address) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(address)
+}
+
+/// Implements a host hash function that writes the hash of the concatenation
+/// of the input slices to the provided address.
+macro_rules! host_hash {
+    ( $(#[$meta:meta])* $name:ident, $hashv:path ) => {
+        $(#[$meta])*
+        ///
+        /// # Safety
+        ///
+        /// `vals` must point to `val_len` valid `&[u8]` slices and `hash_result` must
+        /// be valid for writes of 32 bytes.
+        #[inline(always)]
+        pub unsafe fn $name(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64 {
+            let vals = core::slice::from_raw_parts(vals as *const &[u8], val_len as usize);
+            core::ptr::write(hash_result as *mut [u8; 32], $hashv(vals));
+            crate::SUCCESS
+        }
+    };
+}
+
+host_hash!(
+    /// Compute the SHA-256 hash of the given slices.
+    sol_sha256,
+    sha256::hashv
+);
+
+host_hash!(
+    /// Compute the Keccak-256 hash of the given slices.
+    sol_keccak256,
+    keccak::hashv
+);
+
+host_hash!(
+    /// Compute the BLAKE3 hash of the given slices.
+    sol_blake3,
+    blake3::hashv
+);
+
+/// Validate a point of the curve `curve_id`.
+///
+/// Curve operations are not emulated, so points are reported as invalid unless
+/// the backend installed for the current thread implements them.
+///
+/// # Safety
+///
+/// `point_addr` must point to a valid encoded point and `result` must be valid
+/// for writes.
+#[inline(always)]
+pub unsafe fn sol_curve_validate_point(
+    curve_id: u64,
+    point_addr: *const u8,
+    result: *mut u8,
+) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        let point = &*(point_addr as *const [u8; 32]);
+        let valid = backend::with(|backend| backend.curve_validate_point(curve_id, point));
+        core::hint::black_box(result);
+        !valid.unwrap_or_default() as u64
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        core::hint::black_box((curve_id, point_addr, result));
+        1
+    }
+}
+
+/// Perform a group operation on points of the curve `curve_id`.
+///
+/// Curve operations are not emulated, so this fails unless the backend installed
+/// for the current thread implements them.
+///
+/// # Safety
+///
+/// `left_input_addr` and `right_input_addr` must point to valid inputs of the
+/// operation and `result_point_addr` must be valid for writes of a point.
+#[inline(always)]
+pub unsafe fn sol_curve_group_op(
+    curve_id: u64,
+    group_op: u64,
+    left_input_addr: *const u8,
+    right_input_addr: *const u8,
+    result_point_addr: *mut u8,
+) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        let left = &*(left_input_addr as *const [u8; 32]);
+        let right = &*(right_input_addr as *const [u8; 32]);
+
+        match backend::with(|backend| backend.curve_group_op(curve_id, group_op, left, right))
+            .flatten()
+        {
+            Some(point) => {
+                *(result_point_addr as *mut [u8; 32]) = point;
+                0
+            }
+            None => 1,
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        core::hint::black_box((
+            curve_id,
+            group_op,
+            left_input_addr,
+            right_input_addr,
+            result_point_addr,
+        ));
+        1
+    }
+}
+
+/// Perform a multiscalar multiplication on points of the curve `curve_id`.
+///
+/// Curve operations are not emulated, so this fails unless the backend installed
+/// for the current thread implements them.
+///
+/// # Safety
+///
+/// `scalars_addr` and `points_addr` must point to `points_len` scalars and points,
+/// and `result_point_addr` must be valid for writes of a point.
+#[inline(always)]
+pub unsafe fn sol_curve_multiscalar_mul(
+    curve_id: u64,
+    scalars_addr: *const u8,
+    points_addr: *const u8,
+    points_len: u64,
+    result_point_addr: *mut u8,
+) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        let scalars =
+            core::slice::from_raw_parts(scalars_addr as *const [u8; 32], points_len as usize);
+        let points =
+            core::slice::from_raw_parts(points_addr as *const [u8; 32], points_len as usize);
+
+        match backend::with(|backend| backend.curve_multiscalar_mul(curve_id, scalars, points))
+            .flatten()
+        {
+            Some(point) => {
+                *(result_point_addr as *mut [u8; 32]) = point;
+                0
+            }
+            None => 1,
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        core::hint::black_box((
+            curve_id,
+            scalars_addr,
+            points_addr,
+            points_len,
+            result_point_addr,
+        ));
+        1
+    }
+}
+
+/// Perform an `alt_bn128` group operation.
+///
+/// Curve operations are not emulated, so this fails unless the backend installed
+/// for the current thread implements them.
+///
+/// # Safety
+///
+/// `input` must point to `input_size` valid bytes and `result` must be valid for
+/// writes of the operation output.
+#[inline(always)]
+pub unsafe fn sol_alt_bn128_group_op(
+    group_op: u64,
+    input: *const u8,
+    input_size: u64,
+    result: *mut u8,
+) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        use crate::alt_bn128::{
+            ADD, ADDITION_OUTPUT_LEN, MUL, MULTIPLICATION_OUTPUT_LEN, PAIRING, PAIRING_OUTPUT_LEN,
+        };
+
+        let output_len = match group_op {
+            ADD => ADDITION_OUTPUT_LEN,
+            MUL => MULTIPLICATION_OUTPUT_LEN,
+            PAIRING => PAIRING_OUTPUT_LEN,
+            _ => return 1,
+        };
+        let input = core::slice::from_raw_parts(input, input_size as usize);
+
+        match backend::with(|backend| backend.alt_bn128_group_op(group_op, input)).flatten() {
+            Some(output) if output.len() == output_len => {
+                core::ptr::copy_nonoverlapping(output.as_ptr(), result, output_len);
+                0
+            }
+            _ => 1,
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        core::hint::black_box((group_op, input, input_size, result));
+        1
+    }
+}
+
+/// Compress or decompress an `alt_bn128` point.
+///
+/// Curve operations are not available off-chain, so this always fails.
+///
+/// # Safety
+///
+/// `input` must point to `input_size` valid bytes and `result` must be valid for
+/// writes of the operation output.
+#[inline(always)]
+pub unsafe fn sol_alt_bn128_compression(
+    op: u64,
+    input: *const u8,
+    input_size: u64,
+    result: *mut u8,
+) -> u64 {
+    core::hint::black_box((op, input, input_size, result));
+    1
+}
+
+/// Compute the Poseidon hash of the given slices.
+///
+/// The Poseidon hash is not available off-chain, so this always fails with the
+/// [`Unexpected`](crate::poseidon::PoseidonError::Unexpected) error code.
+///
+/// # Safety
+///
+/// `vals` must point to `val_len` valid `&[u8]` slices and `hash_result` must be
+/// valid for writes of 32 bytes.
+#[inline(always)]
+pub unsafe fn sol_poseidon(
+    parameters: u64,
+    endianness: u64,
+    vals: *const u8,
+    val_len: u64,
+    hash_result: *mut u8,
+) -> u64 {
+    core::hint::black_box((parameters, endianness, vals, val_len, hash_result));
+    crate::poseidon::PoseidonError::Unexpected as u64
+}
+
+/// Compute the modular exponentiation of big integers.
+///
+/// Fails if any of the inputs is longer than [`MAX_LEN`] bytes.
+///
+/// # Safety
+///
+/// `params` must point to valid [`BigModExpParams`] and `result` must be valid for
+/// writes of `modulus_len` bytes.
+#[inline(always)]
+pub unsafe fn sol_big_mod_exp(params: *const u8, result: *mut u8) -> u64 {
+    let params = &*(params as *const BigModExpParams);
+
+    if params.base_len > MAX_LEN as u64
+        || params.exponent_len > MAX_LEN as u64
+        || params.modulus_len > MAX_LEN as u64
+    {
+        return 1;
+    }
+
+    big_mod_exp::big_mod_exp(
+        core::slice::from_raw_parts(params.base, params.base_len as usize),
+        core::slice::from_raw_parts(params.exponent, params.exponent_len as usize),
+        core::slice::from_raw_parts(params.modulus, params.modulus_len as usize),
+        core::slice::from_raw_parts_mut(result, params.modulus_len as usize),
+    );
+
+    crate::SUCCESS
+}
+
+/// Copy `length` bytes of the account data of a sysvar starting at `offset`.
+///
+/// # Safety
+///
+/// `sysvar_id_addr` must point to a valid `Pubkey` and `result` must be valid for
+/// writes of `length` bytes.
+#[inline(always)]
+pub unsafe fn sol_get_sysvar(
+    sysvar_id_addr: *const u8,
+    result: *mut u8,
+    offset: u64,
+    length: u64,
+) -> u64 {
+    #[cfg(feature = "std")]
+    {
+        use crate::sysvars::{OFFSET_LENGTH_EXCEEDS_SYSVAR, SYSVAR_NOT_FOUND};
+
+        let id = &*(sysvar_id_addr as *const Pubkey);
+
+        match backend::with(|backend| backend.get_sysvar_data(id)).flatten() {
+            Some(data) => match offset.checked_add(length) {
+                Some(end) if end <= data.len() as u64 => {
+                    core::ptr::copy_nonoverlapping(
+                        data.as_ptr().add(offset as usize),
+                        result,
+                        length as usize,
+                    );
+                    crate::SUCCESS
+                }
+                _ => OFFSET_LENGTH_EXCEEDS_SYSVAR,
+            },
+            None => SYSVAR_NOT_FOUND,
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        core::hint::black_box((sysvar_id_addr, result, offset, length));
+        crate::sysvars::SYSVAR_NOT_FOUND
+    }
+}
+
+/// Implements a host sysvar function that writes the value returned by the
+/// backend to the provided address.
+macro_rules! host_sysvar_get {
+    ( $(#[$meta:meta])* $name:ident, $getter:ident, $sysvar:ty ) => {
+        $(#[$meta])*
+        ///
+        /// # Safety
+        ///
+        /// `addr` must be valid for writes of the sysvar type.
+        #[inline(always)]
+        pub unsafe fn $name(addr: *mut u8) -> u64 {
+            #[cfg(feature = "std")]
+            {
+                match backend::with(|backend| backend.$getter()) {
+                    Some(Ok(sysvar)) => {
+                        core::ptr::write(addr as *mut $sysvar, sysvar.into());
+                        crate::SUCCESS
+                    }
+                    Some(Err(error)) => error.into(),
+                    None => crate::program_error::UNSUPPORTED_SYSVAR,
+                }
+            }
+
+            #[cfg(not(feature = "std"))]
+            core::hint::black_box(addr as *const _ as u64)
+        }
+    };
+}
+
+host_sysvar_get!(
+    /// Get the `Clock` sysvar.
+    sol_get_clock_sysvar,
+    get_clock,
+    crate::sysvars::clock::Clock
+);
+
+host_sysvar_get!(
+    /// Get the `EpochRewards` sysvar.
+    sol_get_epoch_rewards_sysvar,
+    get_epoch_rewards,
+    crate::sysvars::epoch_rewards::SyscallEpochRewards
+);
+
+host_sysvar_get!(
+    /// Get the `EpochSchedule` sysvar.
+    sol_get_epoch_schedule_sysvar,
+    get_epoch_schedule,
+    crate::sysvars::epoch_schedule::SyscallEpochSchedule
+);
+
+host_sysvar_get!(
+    /// Get the `Fees` sysvar.
+    sol_get_fees_sysvar,
+    get_fees,
+    crate::sysvars::fees::Fees
+);
+
+host_sysvar_get!(
+    /// Get the `LastRestartSlot` sysvar.
+    sol_get_last_restart_slot,
+    get_last_restart_slot,
+    crate::sysvars::last_restart_slot::LastRestartSlot
+);
+
+host_sysvar_get!(
+    /// Get the `Rent` sysvar.
+    sol_get_rent_sysvar,
+    get_rent,
+    crate::sysvars::rent::Rent
+);
+
+#[cfg(feature = "std")]
+mod backend;
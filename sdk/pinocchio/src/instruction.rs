@@ -1,8 +1,8 @@
 //! Instruction types.
 
-use core::{marker::PhantomData, ops::Deref};
+use core::{marker::PhantomData, mem::MaybeUninit, ops::Deref};
 
-use crate::{account_info::AccountInfo, pubkey::Pubkey};
+use crate::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
 /// Information about a CPI instruction.
 #[derive(Debug, Clone)]
@@ -32,6 +32,136 @@ pub struct ProcessedSiblingInstruction {
     pub accounts_len: u64,
 }
 
+/// An account of a processed sibling instruction, as written by the
+/// `sol_get_processed_sibling_instruction` syscall.
+#[repr(C)]
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ProcessedAccountMeta {
+    /// Public key of the account.
+    pub pubkey: Pubkey,
+
+    /// Indicates whether the account signed the instruction or not.
+    pub is_signer: bool,
+
+    /// Indicates whether the account is writable or not.
+    pub is_writable: bool,
+}
+
+/// A processed sibling instruction, holding up to `MAX_DATA` bytes of instruction
+/// data and `MAX_ACCOUNTS` accounts.
+pub struct ProcessedInstruction<const MAX_DATA: usize, const MAX_ACCOUNTS: usize> {
+    /// Program invoked by the instruction.
+    program_id: Pubkey,
+
+    /// Data of the instruction.
+    data: [MaybeUninit<u8>; MAX_DATA],
+
+    /// Length of the instruction data.
+    data_len: usize,
+
+    /// Accounts of the instruction.
+    accounts: [MaybeUninit<ProcessedAccountMeta>; MAX_ACCOUNTS],
+
+    /// Number of accounts of the instruction.
+    accounts_len: usize,
+}
+
+impl<const MAX_DATA: usize, const MAX_ACCOUNTS: usize>
+    ProcessedInstruction<MAX_DATA, MAX_ACCOUNTS>
+{
+    /// Returns the program invoked by the instruction.
+    #[inline(always)]
+    pub fn program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    /// Returns the data of the instruction.
+    #[inline(always)]
+    pub fn data(&self) -> &[u8] {
+        // SAFETY: The first `data_len` bytes were written by the syscall.
+        unsafe { core::slice::from_raw_parts(self.data.as_ptr() as _, self.data_len) }
+    }
+
+    /// Returns the accounts of the instruction.
+    #[inline(always)]
+    pub fn accounts(&self) -> &[ProcessedAccountMeta] {
+        // SAFETY: The first `accounts_len` accounts were written by the syscall.
+        unsafe { core::slice::from_raw_parts(self.accounts.as_ptr() as _, self.accounts_len) }
+    }
+}
+
+/// Returns a sibling instruction from the processed sibling instruction list.
+///
+/// The processed sibling instruction list is a reverse-ordered list of
+/// successfully processed instructions at the same stack height as the current
+/// instruction, i.e., `index` 0 is the most recently processed sibling.
+///
+/// Returns `None` if there is no sibling instruction at `index`, and
+/// [`ProgramError::InvalidArgument`] if the instruction has more than `MAX_DATA`
+/// bytes of data or more than `MAX_ACCOUNTS` accounts.
+///
+/// This does not require the instructions sysvar account.
+pub fn get_processed_sibling_instruction<const MAX_DATA: usize, const MAX_ACCOUNTS: usize>(
+    index: usize,
+) -> Result<Option<ProcessedInstruction<MAX_DATA, MAX_ACCOUNTS>>, ProgramError> {
+    const UNINIT_BYTE: MaybeUninit<u8> = MaybeUninit::<u8>::uninit();
+    const UNINIT_META: MaybeUninit<ProcessedAccountMeta> =
+        MaybeUninit::<ProcessedAccountMeta>::uninit();
+
+    let mut instruction = ProcessedInstruction {
+        program_id: Pubkey::default(),
+        data: [UNINIT_BYTE; MAX_DATA],
+        data_len: 0,
+        accounts: [UNINIT_META; MAX_ACCOUNTS],
+        accounts_len: 0,
+    };
+
+    // The syscall only copies the instruction when the lengths of the buffers match
+    // the lengths of the instruction, so it is called first with empty buffers to
+    // read the lengths.
+    let mut meta = ProcessedSiblingInstruction::default();
+
+    loop {
+        let requested = meta;
+
+        #[cfg(target_os = "solana")]
+        let found = unsafe {
+            crate::syscalls::sol_get_processed_sibling_instruction(
+                index as u64,
+                &mut meta,
+                &mut instruction.program_id,
+                instruction.data.as_mut_ptr() as *mut u8,
+                instruction.accounts.as_mut_ptr() as *mut AccountMeta,
+            )
+        };
+
+        #[cfg(not(target_os = "solana"))]
+        let found = unsafe {
+            crate::host::sol_get_processed_sibling_instruction(
+                index as u64,
+                &mut meta,
+                &mut instruction.program_id,
+                instruction.data.as_mut_ptr() as *mut u8,
+                instruction.accounts.as_mut_ptr() as *mut AccountMeta,
+            )
+        };
+
+        if found == 0 {
+            return Ok(None);
+        }
+
+        if meta == requested {
+            instruction.data_len = meta.data_len as usize;
+            instruction.accounts_len = meta.accounts_len as usize;
+            return Ok(Some(instruction));
+        }
+
+        if meta.data_len > MAX_DATA as u64 || meta.accounts_len > MAX_ACCOUNTS as u64 {
+            return Err(ProgramError::InvalidArgument);
+        }
+    }
+}
+
 /// An `Account` for CPI invocations.
 ///
 /// This struct contains the same information as an [`AccountInfo`], but has
@@ -298,3 +428,57 @@ macro_rules! seeds {
         )*]
     };
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::host::{set_backend, SiblingInstruction, TestBackend};
+    use std::{rc::Rc, vec};
+
+    #[test]
+    fn test_processed_sibling_instruction() {
+        let account = ProcessedAccountMeta {
+            pubkey: [2u8; 32],
+            is_signer: true,
+            is_writable: false,
+        };
+        let backend = Rc::new(TestBackend {
+            processed_sibling_instructions: vec![
+                SiblingInstruction {
+                    program_id: [1u8; 32],
+                    accounts: vec![account],
+                    data: vec![1, 2, 3],
+                },
+                SiblingInstruction {
+                    program_id: [3u8; 32],
+                    ..SiblingInstruction::default()
+                },
+            ],
+            ..TestBackend::default()
+        });
+        let _guard = set_backend(backend);
+
+        let instruction = get_processed_sibling_instruction::<8, 2>(0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(instruction.program_id(), &[1u8; 32]);
+        assert_eq!(instruction.accounts(), &[account]);
+        assert_eq!(instruction.data(), &[1, 2, 3]);
+
+        let instruction = get_processed_sibling_instruction::<0, 0>(1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(instruction.program_id(), &[3u8; 32]);
+        assert!(instruction.data().is_empty());
+
+        assert!(get_processed_sibling_instruction::<8, 2>(2)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            get_processed_sibling_instruction::<2, 2>(0).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,656 @@
+//! Encoding and decoding of instruction data.
+//!
+//! The [`InstructionData`] trait packs values into a stack buffer and unpacks them
+//! from the instruction data without allocating. Values are encoded as follows:
+//!
+//! - integers are little-endian and `bool` is a single `0` or `1` byte;
+//! - fixed-size arrays encode their elements in order, so a `Pubkey` is encoded
+//!   as its bytes, and references to [`Pod`] types are unpacked without copying;
+//! - `Option<T>` is encoded with a `u8` flag, followed by the value only when
+//!   present &mdash; the encoding used by the SPL Token program instructions;
+//! - [`COption<T>`] is encoded with a `u32` flag, always followed by the value;
+//! - [`Bytes`] and [`Str`] are encoded with a `u32` length prefix.
+//!
+//! Since every type has a maximum encoded length, [`InstructionData::MAX_LEN`] can
+//! be used to size the buffer of the instruction data at compile time. With the
+//! `derive` feature enabled, `InstructionData` can be derived for structs, which
+//! encode their fields in order, and enums, which encode a `u8` discriminator
+//! followed by the fields of the variant.
+//!
+//! # Example
+//!
+//! ```
+//! use core::mem::MaybeUninit;
+//! use pinocchio::{
+//!     instruction_data::{DataReader, DataWriter, InstructionData},
+//!     program_error::ProgramError,
+//!     pubkey::Pubkey,
+//! };
+//!
+//! struct Transfer<'a> {
+//!     amount: u64,
+//!     destination: &'a Pubkey,
+//! }
+//!
+//! impl<'a> InstructionData<'a> for Transfer<'a> {
+//!     const MAX_LEN: usize = u64::MAX_LEN + <&Pubkey>::MAX_LEN;
+//!
+//!     fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError> {
+//!         writer.write(&self.amount)?;
+//!         writer.write(&self.destination)
+//!     }
+//!
+//!     fn unpack(reader: &mut DataReader<'a>) -> Result<Self, ProgramError> {
+//!         Ok(Self {
+//!             amount: reader.read()?,
+//!             destination: reader.read()?,
+//!         })
+//!     }
+//! }
+//!
+//! let destination = [7; 32];
+//! let transfer = Transfer { amount: 100, destination: &destination };
+//!
+//! let mut buffer = [MaybeUninit::<u8>::uninit(); Transfer::MAX_LEN];
+//! let data = transfer.pack_into(&mut buffer).unwrap();
+//!
+//! let transfer = Transfer::unpack_from_slice(data).unwrap();
+//! assert_eq!(transfer.amount, 100);
+//! assert_eq!(transfer.destination, &destination);
+//! ```
+
+use core::mem::{size_of, MaybeUninit};
+
+use crate::{
+    pod::{COption, Pod},
+    program_error::ProgramError,
+};
+
+#[cfg(feature = "derive")]
+pub use pinocchio_derive::InstructionData;
+
+/// Length of the prefix of [`Bytes`] and [`Str`].
+const LEN_PREFIX: usize = size_of::<u32>();
+
+/// A value that can be packed into and unpacked from instruction data.
+///
+/// Values unpacked from instruction data may borrow from it for the lifetime `'a`.
+pub trait InstructionData<'a>: Sized {
+    /// The maximum length of the packed value.
+    const MAX_LEN: usize;
+
+    /// Pack the value into `writer`.
+    fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError>;
+
+    /// Unpack a value from `reader`.
+    fn unpack(reader: &mut DataReader<'a>) -> Result<Self, ProgramError>;
+
+    /// Pack the value into `buffer`, returning the packed bytes.
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if `buffer` is too small.
+    #[inline]
+    fn pack_into<'b>(&self, buffer: &'b mut [MaybeUninit<u8>]) -> Result<&'b [u8], ProgramError> {
+        let mut writer = DataWriter::new(buffer);
+        self.pack(&mut writer)?;
+        Ok(writer.into_written())
+    }
+
+    /// Unpack a value from `data`, requiring all the bytes to be consumed.
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if `data` does not hold a
+    /// valid value or has trailing bytes.
+    #[inline]
+    fn unpack_from_slice(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let mut reader = DataReader::new(data);
+        let value = Self::unpack(&mut reader)?;
+        reader.finish()?;
+        Ok(value)
+    }
+}
+
+/// A cursor to read values from instruction data.
+///
+/// Reading past the end of the data returns
+/// [`ProgramError::InvalidInstructionData`].
+#[derive(Clone, Debug)]
+pub struct DataReader<'a> {
+    /// The instruction data.
+    data: &'a [u8],
+
+    /// Offset of the next byte to read.
+    offset: usize,
+}
+
+impl<'a> DataReader<'a> {
+    /// Create a new `DataReader` at the start of `data`.
+    #[inline(always)]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    /// Return the offset of the next byte to read.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the bytes that were not read yet.
+    #[inline(always)]
+    pub fn remaining(&self) -> &'a [u8] {
+        // SAFETY: The offset is never past the end of the data.
+        unsafe { self.data.get_unchecked(self.offset..) }
+    }
+
+    /// Return whether all bytes were read.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.offset == self.data.len()
+    }
+
+    /// Require that all bytes were read.
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if there are bytes left.
+    #[inline(always)]
+    pub fn finish(&self) -> Result<(), ProgramError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+
+    /// Read the next `len` bytes.
+    #[inline(always)]
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], ProgramError> {
+        if len > self.data.len() - self.offset {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        // SAFETY: The range was checked to be within the data.
+        let bytes = unsafe { self.data.get_unchecked(self.offset..self.offset + len) };
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    /// Read a reference to the next `N` bytes.
+    #[inline(always)]
+    pub fn read_array<const N: usize>(&mut self) -> Result<&'a [u8; N], ProgramError> {
+        let bytes = self.read_bytes(N)?;
+        // SAFETY: `bytes` is `N` bytes long.
+        Ok(unsafe { &*(bytes.as_ptr() as *const [u8; N]) })
+    }
+
+    /// Read the next byte.
+    #[inline(always)]
+    pub fn read_u8(&mut self) -> Result<u8, ProgramError> {
+        self.read_array::<1>().map(|bytes| bytes[0])
+    }
+
+    /// Read a reference to the next `T`, without copying it.
+    #[inline(always)]
+    pub fn read_ref<T: Pod>(&mut self) -> Result<&'a T, ProgramError> {
+        let bytes = self.read_bytes(size_of::<T>())?;
+        // SAFETY: `T` is `Pod` and `bytes` has the size of `T`.
+        Ok(unsafe { &*(bytes.as_ptr() as *const T) })
+    }
+
+    /// Read the next value.
+    #[inline(always)]
+    pub fn read<T: InstructionData<'a>>(&mut self) -> Result<T, ProgramError> {
+        T::unpack(self)
+    }
+}
+
+/// A cursor to write values into a buffer of instruction data.
+///
+/// Writing past the end of the buffer returns [`ProgramError::InvalidArgument`].
+pub struct DataWriter<'b> {
+    /// The buffer.
+    buffer: &'b mut [MaybeUninit<u8>],
+
+    /// Number of bytes written.
+    len: usize,
+}
+
+impl<'b> DataWriter<'b> {
+    /// Create a new `DataWriter` at the start of `buffer`.
+    #[inline(always)]
+    pub fn new(buffer: &'b mut [MaybeUninit<u8>]) -> Self {
+        Self { buffer, len: 0 }
+    }
+
+    /// Return the number of bytes written.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether no bytes were written.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the bytes written.
+    #[inline(always)]
+    pub fn written(&self) -> &[u8] {
+        // SAFETY: The first `len` bytes were written.
+        unsafe { core::slice::from_raw_parts(self.buffer.as_ptr() as *const u8, self.len) }
+    }
+
+    /// Return the bytes written, consuming the writer.
+    #[inline(always)]
+    pub fn into_written(self) -> &'b [u8] {
+        // SAFETY: The first `len` bytes were written.
+        unsafe { core::slice::from_raw_parts(self.buffer.as_ptr() as *const u8, self.len) }
+    }
+
+    /// Write the given bytes.
+    #[inline(always)]
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ProgramError> {
+        if bytes.len() > self.buffer.len() - self.len {
+            return Err(ProgramError::InvalidArgument);
+        }
+        // SAFETY: The range was checked to be within the buffer.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                self.buffer.as_mut_ptr().add(self.len) as *mut u8,
+                bytes.len(),
+            );
+        }
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    /// Write a byte.
+    #[inline(always)]
+    pub fn write_u8(&mut self, value: u8) -> Result<(), ProgramError> {
+        self.write_bytes(&[value])
+    }
+
+    /// Write the given value.
+    #[inline(always)]
+    pub fn write<'a, T: InstructionData<'a>>(&mut self, value: &T) -> Result<(), ProgramError> {
+        value.pack(self)
+    }
+}
+
+/// Implements [`InstructionData`] for integer types using little-endian encoding.
+macro_rules! impl_integer {
+    ( $( $type:ty ),* ) => {
+        $(
+            impl InstructionData<'_> for $type {
+                const MAX_LEN: usize = size_of::<$type>();
+
+                #[inline(always)]
+                fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError> {
+                    writer.write_bytes(&self.to_le_bytes())
+                }
+
+                #[inline(always)]
+                fn unpack(reader: &mut DataReader) -> Result<Self, ProgramError> {
+                    reader.read_array().map(|bytes| <$type>::from_le_bytes(*bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl InstructionData<'_> for bool {
+    const MAX_LEN: usize = 1;
+
+    #[inline(always)]
+    fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError> {
+        writer.write_u8(*self as u8)
+    }
+
+    #[inline(always)]
+    fn unpack(reader: &mut DataReader) -> Result<Self, ProgramError> {
+        match reader.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+impl<'a, T: InstructionData<'a>, const N: usize> InstructionData<'a> for [T; N] {
+    const MAX_LEN: usize = N * T::MAX_LEN;
+
+    #[inline(always)]
+    fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError> {
+        for element in self {
+            element.pack(writer)?;
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn unpack(reader: &mut DataReader<'a>) -> Result<Self, ProgramError> {
+        let mut array = [const { MaybeUninit::<T>::uninit() }; N];
+        for element in array.iter_mut() {
+            element.write(T::unpack(reader)?);
+        }
+        // SAFETY: All the elements were initialized.
+        Ok(unsafe { core::ptr::read(array.as_ptr() as *const [T; N]) })
+    }
+}
+
+impl<'a, T: Pod> InstructionData<'a> for &'a T {
+    const MAX_LEN: usize = size_of::<T>();
+
+    #[inline(always)]
+    fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError> {
+        writer.write_bytes(crate::pod::bytes_of(*self))
+    }
+
+    #[inline(always)]
+    fn unpack(reader: &mut DataReader<'a>) -> Result<Self, ProgramError> {
+        reader.read_ref()
+    }
+}
+
+impl<'a, T: InstructionData<'a>> InstructionData<'a> for Option<T> {
+    const MAX_LEN: usize = 1 + T::MAX_LEN;
+
+    #[inline(always)]
+    fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError> {
+        match self {
+            Some(value) => {
+                writer.write_u8(1)?;
+                value.pack(writer)
+            }
+            None => writer.write_u8(0),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(reader: &mut DataReader<'a>) -> Result<Self, ProgramError> {
+        match reader.read_u8()? {
+            0 => Ok(None),
+            1 => T::unpack(reader).map(Some),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+impl<T: Pod> InstructionData<'_> for COption<T> {
+    const MAX_LEN: usize = size_of::<COption<T>>();
+
+    #[inline(always)]
+    fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError> {
+        writer.write_bytes(crate::pod::bytes_of(self))
+    }
+
+    #[inline(always)]
+    fn unpack(reader: &mut DataReader) -> Result<Self, ProgramError> {
+        let bytes = reader.read_bytes(size_of::<COption<T>>())?;
+
+        if bytes[1..4] != [0, 0, 0] || bytes[0] > 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // SAFETY: `COption<T>` is `Pod` and `bytes` has the size of `COption<T>`.
+        Ok(unsafe { core::ptr::read(bytes.as_ptr() as *const COption<T>) })
+    }
+}
+
+/// Bytes encoded with a `u32` length prefix, holding at most `MAX` bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bytes<'a, const MAX: usize>(pub &'a [u8]);
+
+impl<'a, const MAX: usize> InstructionData<'a> for Bytes<'a, MAX> {
+    const MAX_LEN: usize = LEN_PREFIX + MAX;
+
+    #[inline(always)]
+    fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError> {
+        if self.0.len() > MAX {
+            return Err(ProgramError::InvalidArgument);
+        }
+        writer.write_bytes(&(self.0.len() as u32).to_le_bytes())?;
+        writer.write_bytes(self.0)
+    }
+
+    #[inline(always)]
+    fn unpack(reader: &mut DataReader<'a>) -> Result<Self, ProgramError> {
+        let len = u32::unpack(reader)? as usize;
+        if len > MAX {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        reader.read_bytes(len).map(Bytes)
+    }
+}
+
+/// A UTF-8 string encoded with a `u32` length prefix, holding at most `MAX` bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Str<'a, const MAX: usize>(pub &'a str);
+
+impl<'a, const MAX: usize> InstructionData<'a> for Str<'a, MAX> {
+    const MAX_LEN: usize = LEN_PREFIX + MAX;
+
+    #[inline(always)]
+    fn pack(&self, writer: &mut DataWriter) -> Result<(), ProgramError> {
+        Bytes::<MAX>(self.0.as_bytes()).pack(writer)
+    }
+
+    #[inline(always)]
+    fn unpack(reader: &mut DataReader<'a>) -> Result<Self, ProgramError> {
+        let bytes = Bytes::<MAX>::unpack(reader)?;
+        core::str::from_utf8(bytes.0)
+            .map(Str)
+            .map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pubkey::Pubkey;
+
+    const UNINIT_BYTE: MaybeUninit<u8> = MaybeUninit::<u8>::uninit();
+
+    #[test]
+    fn test_reader() {
+        let data = [1, 2, 0, 3, 0, 0, 0, 4];
+        let mut reader = DataReader::new(&data);
+
+        assert_eq!(reader.read_u8(), Ok(1));
+        assert_eq!(reader.read::<u16>(), Ok(2));
+        assert_eq!(reader.position(), 3);
+        assert_eq!(reader.read::<u32>(), Ok(3));
+        assert_eq!(reader.finish(), Err(ProgramError::InvalidInstructionData));
+        assert_eq!(reader.remaining(), &[4]);
+        assert_eq!(
+            reader.read::<u16>(),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            reader.read::<bool>(),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_writer() {
+        let mut buffer = [UNINIT_BYTE; 4];
+        let mut writer = DataWriter::new(&mut buffer);
+
+        assert_eq!(writer.write(&1u8), Ok(()));
+        assert_eq!(writer.write(&-2i16), Ok(()));
+        assert_eq!(writer.write(&3u16), Err(ProgramError::InvalidArgument));
+        assert_eq!(writer.written(), &[1, 0xfe, 0xff]);
+        assert_eq!(writer.len(), 3);
+    }
+
+    #[test]
+    fn test_array() {
+        let mut buffer = [UNINIT_BYTE; <[u16; 3]>::MAX_LEN];
+        assert_eq!(buffer.len(), 6);
+
+        let data = [1u16, 2, 0x0304].pack_into(&mut buffer).unwrap();
+        assert_eq!(data, &[1, 0, 2, 0, 4, 3]);
+        assert_eq!(<[u16; 3]>::unpack_from_slice(data), Ok([1, 2, 0x0304]));
+        assert_eq!(
+            <[u16; 3]>::unpack_from_slice(&data[..5]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            <[bool; 2]>::unpack_from_slice(&[1, 2]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_option() {
+        let mut buffer = [UNINIT_BYTE; <Option<Pubkey>>::MAX_LEN];
+        assert_eq!(buffer.len(), 33);
+
+        assert_eq!(None::<Pubkey>.pack_into(&mut buffer), Ok(&[0][..]));
+
+        let data = Some([7u8; 32]).pack_into(&mut buffer).unwrap();
+        assert_eq!(data.len(), 33);
+        assert_eq!(data[0], 1);
+        assert_eq!(<Option<Pubkey>>::unpack_from_slice(data), Ok(Some([7; 32])));
+
+        assert_eq!(
+            <Option<Pubkey>>::unpack_from_slice(&[2]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            <Option<&Pubkey>>::unpack_from_slice(&[0, 0]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_coption() {
+        let mut buffer = [UNINIT_BYTE; <COption<Pubkey>>::MAX_LEN];
+        assert_eq!(buffer.len(), 36);
+
+        let data = COption::from(None::<Pubkey>)
+            .pack_into(&mut buffer)
+            .unwrap();
+        assert_eq!(data, &[0; 36]);
+
+        let data = COption::some([7u8; 32]).pack_into(&mut buffer).unwrap();
+        assert_eq!(data[..4], [1, 0, 0, 0]);
+        assert_eq!(
+            <COption<Pubkey>>::unpack_from_slice(data),
+            Ok(COption::some([7; 32]))
+        );
+
+        let mut data = [0; 36];
+        data[1] = 1;
+        assert_eq!(
+            <COption<Pubkey>>::unpack_from_slice(&data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let mut buffer = [UNINIT_BYTE; <Str<4>>::MAX_LEN];
+
+        let data = Str::<4>("abc").pack_into(&mut buffer).unwrap();
+        assert_eq!(data, &[3, 0, 0, 0, b'a', b'b', b'c']);
+        assert_eq!(Str::<4>::unpack_from_slice(data), Ok(Str("abc")));
+        assert_eq!(
+            Str::<2>::unpack_from_slice(data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            Str::<4>::unpack_from_slice(&[1, 0, 0, 0, 0xff]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            Bytes::<4>::unpack_from_slice(&[1, 0, 0, 0, 0xff]),
+            Ok(Bytes(&[0xff]))
+        );
+
+        assert_eq!(
+            Bytes::<4>(&[0; 5]).pack_into(&mut buffer),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn test_derive() {
+        #[derive(Debug, PartialEq, InstructionData)]
+        struct InitializeMint<'a> {
+            decimals: u8,
+            mint_authority: &'a Pubkey,
+            freeze_authority: Option<Pubkey>,
+        }
+
+        #[derive(Debug, PartialEq, InstructionData)]
+        struct Weights {
+            amounts: [u64; 2],
+            weights: [u16; 3],
+        }
+
+        #[repr(u8)]
+        #[derive(Debug, PartialEq, InstructionData)]
+        enum Instruction<'a> {
+            InitializeMint(InitializeMint<'a>),
+            Transfer { amount: u64 },
+            Memo(Str<'a, 8>),
+            Close = 9,
+            Freeze,
+        }
+
+        assert_eq!(InitializeMint::MAX_LEN, 1 + 32 + 33);
+        assert_eq!(Instruction::MAX_LEN, 1 + InitializeMint::MAX_LEN);
+
+        let authority = [5; 32];
+        let mut buffer = [UNINIT_BYTE; Instruction::MAX_LEN];
+
+        let instruction = Instruction::InitializeMint(InitializeMint {
+            decimals: 6,
+            mint_authority: &authority,
+            freeze_authority: None,
+        });
+        let data = instruction.pack_into(&mut buffer).unwrap();
+        assert_eq!(data.len(), 1 + 1 + 32 + 1);
+        assert_eq!(data[..2], [0, 6]);
+        assert_eq!(Instruction::unpack_from_slice(data), Ok(instruction));
+
+        let instruction = Instruction::Transfer { amount: 42 };
+        let data = instruction.pack_into(&mut buffer).unwrap();
+        assert_eq!(data, &[1, 42, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(Instruction::unpack_from_slice(data), Ok(instruction));
+
+        let data = Instruction::Memo(Str("hi")).pack_into(&mut buffer).unwrap();
+        assert_eq!(data, &[2, 2, 0, 0, 0, b'h', b'i']);
+
+        assert_eq!(Instruction::Close.pack_into(&mut buffer), Ok(&[9][..]));
+        assert_eq!(
+            Instruction::unpack_from_slice(&[10]),
+            Ok(Instruction::Freeze)
+        );
+        assert_eq!(
+            Instruction::unpack_from_slice(&[3]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            Instruction::unpack_from_slice(&[9, 0]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        assert_eq!(Weights::MAX_LEN, 2 * 8 + 3 * 2);
+
+        let weights = Weights {
+            amounts: [1, u64::MAX],
+            weights: [2, 3, 4],
+        };
+        let mut buffer = [UNINIT_BYTE; Weights::MAX_LEN];
+        let data = weights.pack_into(&mut buffer).unwrap();
+        assert_eq!(data[..8], 1u64.to_le_bytes());
+        assert_eq!(data[8..16], [0xff; 8]);
+        assert_eq!(data[16..], [2, 0, 3, 0, 4, 0]);
+        assert_eq!(Weights::unpack_from_slice(data), Ok(weights));
+    }
+}
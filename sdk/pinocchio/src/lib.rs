@@ -223,18 +223,35 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+// Allows the code generated by the derive macros to refer to `::pinocchio` within
+// this crate.
+#[cfg(feature = "derive")]
+extern crate self as pinocchio;
+
 pub mod account_info;
+pub mod alt_bn128;
+pub mod big_mod_exp;
+pub mod compute;
 pub mod cpi;
+pub mod curve25519;
 pub mod entrypoint;
+pub mod hash;
+#[cfg(not(target_os = "solana"))]
+pub mod host;
 pub mod instruction;
+pub mod instruction_data;
 pub mod log;
 pub mod memory;
+pub mod pod;
+pub mod poseidon;
+pub mod precompiles;
 #[deprecated(since = "0.8.0", note = "Use the `cpi` module instead")]
 pub mod program {
     pub use crate::cpi::*;
 }
 pub mod program_error;
 pub mod pubkey;
+pub mod stake;
 pub mod syscalls;
 pub mod sysvars;
 
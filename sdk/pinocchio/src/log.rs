@@ -100,7 +100,9 @@ pub fn sol_log(message: &str) {
     }
 
     #[cfg(not(target_os = "solana"))]
-    core::hint::black_box(message);
+    unsafe {
+        crate::host::sol_log_(message.as_ptr(), message.len() as u64);
+    }
 }
 
 /// Print 64-bit values represented as hexadecimal to the log.
@@ -112,7 +114,7 @@ pub fn sol_log_64(arg1: u64, arg2: u64, arg3: u64, arg4: u64, arg5: u64) {
     }
 
     #[cfg(not(target_os = "solana"))]
-    core::hint::black_box((arg1, arg2, arg3, arg4, arg5));
+    crate::host::sol_log_64_(arg1, arg2, arg3, arg4, arg5);
 }
 
 /// Print some slices as base64.
@@ -123,7 +125,9 @@ pub fn sol_log_data(data: &[&[u8]]) {
     };
 
     #[cfg(not(target_os = "solana"))]
-    core::hint::black_box(data);
+    unsafe {
+        crate::host::sol_log_data(data as *const _ as *const u8, data.len() as u64)
+    };
 }
 
 /// Print the hexadecimal representation of a slice.
@@ -164,4 +168,7 @@ pub fn sol_log_compute_units() {
     unsafe {
         crate::syscalls::sol_log_compute_units_();
     }
+
+    #[cfg(not(target_os = "solana"))]
+    crate::host::sol_log_compute_units_();
 }
@@ -0,0 +1,419 @@
+//! Zero-copy access to typed account data.
+//!
+//! Account data is commonly accessed by casting the bytes of the account to a
+//! `#[repr(C)]` type. The [`Pod`] trait marks the types for which this is sound,
+//! and [`AccountLoad`] describes how a type is stored in an account &mdash; its
+//! owner and an optional discriminator &mdash; so it can be loaded with
+//! [`AccountInfo::load`](crate::account_info::AccountInfo::load) and
+//! [`AccountInfo::load_mut`](crate::account_info::AccountInfo::load_mut) after
+//! validating the account.
+//!
+//! With the `derive` feature enabled, `Pod` can be derived for structs. The derive
+//! asserts at compile time that the struct is `#[repr(C)]`, that all its fields are
+//! `Pod` and that it has no padding.
+//!
+//! # Example
+//!
+//! ```
+//! use pinocchio::{
+//!     pod::{account_data_len, AccountLoad, Pod},
+//!     pubkey::Pubkey,
+//! };
+//!
+//! #[repr(C)]
+//! pub struct Counter {
+//!     authority: Pubkey,
+//!     count: [u8; 8],
+//! }
+//!
+//! // SAFETY: `Counter` is `repr(C)` and only holds byte arrays.
+//! unsafe impl Pod for Counter {}
+//!
+//! impl AccountLoad for Counter {
+//!     const OWNER: Pubkey = [1; 32];
+//!     const DISCRIMINATOR: &'static [u8] = &[7];
+//! }
+//!
+//! let mut data = [0u8; account_data_len::<Counter>()];
+//! data[0] = 7;
+//! data[33..].copy_from_slice(&5u64.to_le_bytes());
+//!
+//! let counter = Counter::from_account_data(&data).unwrap();
+//! assert_eq!(u64::from_le_bytes(counter.count), 5);
+//! ```
+
+use crate::{program_error::ProgramError, pubkey::Pubkey};
+
+#[cfg(feature = "derive")]
+pub use pinocchio_derive::Pod;
+
+/// A type that can be read from and written to any sequence of bytes of its size.
+///
+/// Types implementing `Pod` have an alignment of 1, so they can be loaded from
+/// account data regardless of its alignment. Multi-byte values are usually stored as
+/// little-endian byte arrays and accessed through methods.
+///
+/// # Safety
+///
+/// The implementing type must:
+///
+/// - be `#[repr(C)]` or `#[repr(transparent)]`, with only `Pod` fields;
+/// - have an alignment of 1 and no padding;
+/// - be valid for any bit pattern.
+pub unsafe trait Pod: Sized {}
+
+unsafe impl Pod for u8 {}
+
+unsafe impl Pod for i8 {}
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// Return a reference to a `T` from the given bytes.
+///
+/// Returns [`ProgramError::InvalidAccountData`] if the length of `bytes` is not the
+/// size of `T`.
+#[inline(always)]
+pub fn from_bytes<T: Pod>(bytes: &[u8]) -> Result<&T, ProgramError> {
+    if bytes.len() != core::mem::size_of::<T>() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // SAFETY: `T` is `Pod` and `bytes` has the size of `T`.
+    Ok(unsafe { &*(bytes.as_ptr() as *const T) })
+}
+
+/// Return a mutable reference to a `T` from the given bytes.
+///
+/// Returns [`ProgramError::InvalidAccountData`] if the length of `bytes` is not the
+/// size of `T`.
+#[inline(always)]
+pub fn from_bytes_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut T, ProgramError> {
+    if bytes.len() != core::mem::size_of::<T>() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // SAFETY: `T` is `Pod` and `bytes` has the size of `T`.
+    Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut T) })
+}
+
+/// Return the bytes of the given value.
+#[inline(always)]
+pub fn bytes_of<T: Pod>(value: &T) -> &[u8] {
+    // SAFETY: `T` is `Pod`, so it has no padding.
+    unsafe {
+        core::slice::from_raw_parts(value as *const T as *const u8, core::mem::size_of::<T>())
+    }
+}
+
+/// A type stored in the data of accounts owned by a program.
+///
+/// The account data holds the [`DISCRIMINATOR`](AccountLoad::DISCRIMINATOR)
+/// followed by the value.
+pub trait AccountLoad: Pod {
+    /// The program that owns the accounts.
+    const OWNER: Pubkey;
+
+    /// The bytes at the start of the account data that identify the type.
+    const DISCRIMINATOR: &'static [u8] = &[];
+
+    /// Return the value from the given account data.
+    ///
+    /// This method validates the length and discriminator of the data.
+    #[inline(always)]
+    fn from_account_data(data: &[u8]) -> Result<&Self, ProgramError> {
+        check_account_data::<Self>(data)?;
+        Ok(unsafe { Self::from_account_data_unchecked(data) })
+    }
+
+    /// Return the value from the given account data.
+    ///
+    /// This method validates the length and discriminator of the data.
+    #[inline(always)]
+    fn from_account_data_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        check_account_data::<Self>(data)?;
+        Ok(unsafe { Self::from_account_data_mut_unchecked(data) })
+    }
+
+    /// Return the value from the given account data.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `data` holds at least
+    /// [`account_data_len::<Self>()`](account_data_len) bytes.
+    #[inline(always)]
+    unsafe fn from_account_data_unchecked(data: &[u8]) -> &Self {
+        &*(data.as_ptr().add(Self::DISCRIMINATOR.len()) as *const Self)
+    }
+
+    /// Return the value from the given account data.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `data` holds at least
+    /// [`account_data_len::<Self>()`](account_data_len) bytes.
+    #[inline(always)]
+    unsafe fn from_account_data_mut_unchecked(data: &mut [u8]) -> &mut Self {
+        &mut *(data.as_mut_ptr().add(Self::DISCRIMINATOR.len()) as *mut Self)
+    }
+}
+
+/// Return the length of the account data holding a `T`.
+///
+/// This is the length of the discriminator followed by the size of `T`.
+#[inline(always)]
+pub const fn account_data_len<T: AccountLoad>() -> usize {
+    T::DISCRIMINATOR.len() + core::mem::size_of::<T>()
+}
+
+/// Check that `data` holds a value of type `T`.
+///
+/// Returns [`ProgramError::InvalidAccountData`] if the length of the data is not
+/// [`account_data_len::<T>()`](account_data_len) or the data does not start with
+/// the discriminator.
+#[inline(always)]
+pub fn check_account_data<T: AccountLoad>(data: &[u8]) -> Result<(), ProgramError> {
+    if data.len() != account_data_len::<T>() || !data.starts_with(T::DISCRIMINATOR) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+/// Tag of a [`COption`] without value.
+const NONE: [u8; 4] = [0, 0, 0, 0];
+
+/// Tag of a [`COption`] with a value.
+const SOME: [u8; 4] = [1, 0, 0, 0];
+
+/// A zero-copy optional value.
+///
+/// The value is preceded by a 4-byte tag, which is `[1, 0, 0, 0]` when the value is
+/// present and `[0, 0, 0, 0]` otherwise. This is the layout used by the SPL Token
+/// program for optional authorities.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct COption<T: Pod> {
+    /// Indicates whether the value is present or not.
+    tag: [u8; 4],
+
+    /// The value, which is only meaningful when present.
+    value: T,
+}
+
+unsafe impl<T: Pod> Pod for COption<T> {}
+
+impl<T: Pod> COption<T> {
+    /// Create a `COption` with the given value.
+    #[inline(always)]
+    pub fn some(value: T) -> Self {
+        Self { tag: SOME, value }
+    }
+
+    /// Return whether the value is present.
+    #[inline(always)]
+    pub fn is_some(&self) -> bool {
+        self.tag == SOME
+    }
+
+    /// Return whether the value is not present.
+    #[inline(always)]
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// Return the value, if present.
+    #[inline(always)]
+    pub fn get(&self) -> Option<&T> {
+        if self.is_some() {
+            Some(&self.value)
+        } else {
+            None
+        }
+    }
+
+    /// Return a mutable reference to the value, if present.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if self.is_some() {
+            Some(&mut self.value)
+        } else {
+            None
+        }
+    }
+
+    /// Return the value without checking the tag.
+    ///
+    /// This method should be used when the caller knows that the value is present,
+    /// since it skips the tag check.
+    #[inline(always)]
+    pub fn get_unchecked(&self) -> &T {
+        &self.value
+    }
+
+    /// Set the value.
+    #[inline(always)]
+    pub fn set(&mut self, value: T) {
+        self.tag = SOME;
+        self.value = value;
+    }
+
+    /// Remove the value, zeroing its bytes.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.tag = NONE;
+        // SAFETY: `T` is `Pod`, so it is valid for any bit pattern.
+        unsafe { core::ptr::write_bytes(&mut self.value as *mut T, 0, 1) };
+    }
+}
+
+impl<T: Pod> From<Option<T>> for COption<T> {
+    #[inline(always)]
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::some(value),
+            // SAFETY: `Self` is `Pod`, so it is valid for any bit pattern.
+            None => unsafe { core::mem::zeroed() },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Debug, PartialEq)]
+    struct Counter {
+        authority: COption<Pubkey>,
+        count: [u8; 8],
+    }
+
+    unsafe impl Pod for Counter {}
+
+    impl AccountLoad for Counter {
+        const OWNER: Pubkey = [1; 32];
+        const DISCRIMINATOR: &'static [u8] = &[7, 8];
+    }
+
+    #[test]
+    fn test_from_account_data() {
+        assert_eq!(account_data_len::<Counter>(), 2 + 4 + 32 + 8);
+
+        let mut data = [0u8; account_data_len::<Counter>()];
+        data[..2].copy_from_slice(&[7, 8]);
+
+        let counter = Counter::from_account_data_mut(&mut data).unwrap();
+        assert!(counter.authority.is_none());
+        counter.authority.set([2; 32]);
+        counter.count = 5u64.to_le_bytes();
+
+        assert_eq!(data[2..6], [1, 0, 0, 0]);
+        assert_eq!(data[6..38], [2; 32]);
+
+        let counter = Counter::from_account_data(&data).unwrap();
+        assert_eq!(counter.authority.get(), Some(&[2; 32]));
+        assert_eq!(u64::from_le_bytes(counter.count), 5);
+        assert_eq!(bytes_of(counter), &data[2..]);
+
+        assert_eq!(
+            Counter::from_account_data(&data[..account_data_len::<Counter>() - 1]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        data[1] = 0;
+        assert_eq!(
+            Counter::from_account_data(&data).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_coption() {
+        let mut option = COption::some([3u8; 4]);
+        assert_eq!(option.get(), Some(&[3; 4]));
+
+        *option.get_mut().unwrap() = [4; 4];
+        assert_eq!(option.get_unchecked(), &[4; 4]);
+
+        option.clear();
+        assert!(option.is_none());
+        assert_eq!(option.get(), None);
+        assert_eq!(bytes_of(&option), &[0; 8]);
+
+        assert_eq!(COption::from(Some(1u8)), COption::some(1));
+        assert!(COption::<u8>::from(None).is_none());
+
+        // any tag other than `[1, 0, 0, 0]` is not a value
+        let option: &COption<u8> = from_bytes(&[1, 1, 0, 0, 9]).unwrap();
+        assert!(option.is_none());
+        assert!(from_bytes::<COption<u8>>(&[1, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_load() {
+        use crate::{
+            account_info::AccountInfo,
+            entrypoint::{deserialize, InputAccount, InputBuilder},
+        };
+        use core::mem::MaybeUninit;
+
+        let mut data = [0u8; account_data_len::<Counter>()];
+        data[..2].copy_from_slice(&[7, 8]);
+
+        let mut input = InputBuilder::new(&[1; 32])
+            .account(InputAccount::new([5; 32], Counter::OWNER).data(&data))
+            .account(InputAccount::new([6; 32], [2; 32]).data(&data))
+            .build();
+
+        const UNINIT: MaybeUninit<AccountInfo> = MaybeUninit::<AccountInfo>::uninit();
+        let mut accounts = [UNINIT; 2];
+        unsafe { deserialize::<2>(input.as_mut_ptr(), &mut accounts) };
+        let account = unsafe { accounts[0].assume_init_ref() };
+        let other = unsafe { accounts[1].assume_init_ref() };
+
+        {
+            let mut counter = account.load_mut::<Counter>().unwrap();
+            counter.count = 9u64.to_le_bytes();
+
+            assert_eq!(
+                account.load::<Counter>().err(),
+                Some(ProgramError::AccountBorrowFailed)
+            );
+        }
+
+        let counter = account.load::<Counter>().unwrap();
+        assert_eq!(u64::from_le_bytes(counter.count), 9);
+        assert!(account.load_mut::<Counter>().is_err());
+        drop(counter);
+
+        assert_eq!(
+            other.load::<Counter>().err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+        assert_eq!(
+            unsafe { account.load_unchecked::<Counter>() }
+                .unwrap()
+                .count,
+            9u64.to_le_bytes()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn test_derive() {
+        #[repr(C)]
+        #[derive(Pod)]
+        struct Derived {
+            flag: u8,
+            value: [u8; 8],
+            authority: COption<Pubkey>,
+        }
+
+        #[repr(transparent)]
+        #[derive(Pod)]
+        struct Wrapper(Derived);
+
+        let data = [1u8; 1 + 8 + 36];
+        let wrapper: &Wrapper = from_bytes(&data).unwrap();
+        assert_eq!(wrapper.0.flag, 1);
+        assert_eq!(wrapper.0.value, [1; 8]);
+        assert!(wrapper.0.authority.is_none());
+    }
+}
@@ -0,0 +1,230 @@
+//! Poseidon hash function.
+//!
+//! Provides a safe wrapper over the `sol_poseidon` syscall, which computes the
+//! [Poseidon] hash of up to [`MAX_INPUTS`] inputs, each of them a field element
+//! encoded in at most [`HASH_BYTES`] bytes. The hash is compatible with the
+//! [circom] implementation.
+//!
+//! The hash is only available on-chain. When not compiled for the `solana`
+//! target, hashing fails with [`PoseidonError::Unexpected`].
+//!
+//! [Poseidon]: https://eprint.iacr.org/2019/458
+//! [circom]: https://github.com/iden3/circomlibjs
+
+use crate::program_error::ProgramError;
+
+/// Number of bytes of a Poseidon hash.
+pub const HASH_BYTES: usize = 32;
+
+/// Maximum number of inputs of a Poseidon hash.
+pub const MAX_INPUTS: usize = 12;
+
+/// Parameters of the Poseidon hash function.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parameters {
+    /// BN254 curve scalar field, with an x^5 S-box.
+    Bn254X5 = 0,
+}
+
+/// Byte order of the inputs and the output of the Poseidon hash function.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    /// Big-endian byte order.
+    BigEndian = 0,
+
+    /// Little-endian byte order.
+    LittleEndian = 1,
+}
+
+/// Errors returned by the Poseidon hash function.
+///
+/// The discriminants match the error codes returned by the `sol_poseidon` syscall.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoseidonError {
+    /// Invalid parameters.
+    InvalidParameters = 1,
+
+    /// Invalid endianness.
+    InvalidEndianness,
+
+    /// The number of inputs is zero or greater than [`MAX_INPUTS`].
+    InvalidNumberOfInputs,
+
+    /// An input is empty.
+    EmptyInput,
+
+    /// An input is longer than [`HASH_BYTES`].
+    InvalidInputLength,
+
+    /// An input could not be converted to a field element.
+    BytesToPrimeFieldElement,
+
+    /// An input is not smaller than the field modulus.
+    InputLargerThanModulus,
+
+    /// Failed to convert a vector to an array.
+    VecToArray,
+
+    /// Failed to convert a `u64` to a `u8`.
+    U64Tou8,
+
+    /// Failed to convert bytes to a big integer.
+    BytesToBigInt,
+
+    /// Invalid width of the circom parameters.
+    InvalidWidthCircom,
+
+    /// Unexpected error.
+    Unexpected,
+}
+
+impl From<u64> for PoseidonError {
+    fn from(error: u64) -> Self {
+        match error {
+            1 => Self::InvalidParameters,
+            2 => Self::InvalidEndianness,
+            3 => Self::InvalidNumberOfInputs,
+            4 => Self::EmptyInput,
+            5 => Self::InvalidInputLength,
+            6 => Self::BytesToPrimeFieldElement,
+            7 => Self::InputLargerThanModulus,
+            8 => Self::VecToArray,
+            9 => Self::U64Tou8,
+            10 => Self::BytesToBigInt,
+            11 => Self::InvalidWidthCircom,
+            _ => Self::Unexpected,
+        }
+    }
+}
+
+impl From<PoseidonError> for ProgramError {
+    fn from(_: PoseidonError) -> Self {
+        ProgramError::InvalidArgument
+    }
+}
+
+/// A Poseidon hash value.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoseidonHash(pub [u8; HASH_BYTES]);
+
+impl PoseidonHash {
+    /// Returns the bytes of the hash.
+    #[inline(always)]
+    pub const fn to_bytes(self) -> [u8; HASH_BYTES] {
+        self.0
+    }
+}
+
+/// Returns the Poseidon hash of a single input.
+///
+/// See [`hashv`] for details.
+#[inline]
+pub fn hash(
+    parameters: Parameters,
+    endianness: Endianness,
+    val: &[u8],
+) -> Result<PoseidonHash, PoseidonError> {
+    hashv(parameters, endianness, &[val])
+}
+
+/// Returns the Poseidon hash of the given inputs.
+///
+/// Each input is a field element encoded in at most [`HASH_BYTES`] bytes using the
+/// given `endianness`, which is also used to encode the resulting hash.
+///
+/// Returns [`PoseidonError::InvalidNumberOfInputs`] if there are no inputs or more
+/// than [`MAX_INPUTS`], [`PoseidonError::EmptyInput`] or
+/// [`PoseidonError::InvalidInputLength`] if an input is empty or longer than
+/// [`HASH_BYTES`], and [`PoseidonError::InputLargerThanModulus`] if an input is not
+/// smaller than the field modulus.
+pub fn hashv(
+    parameters: Parameters,
+    endianness: Endianness,
+    vals: &[&[u8]],
+) -> Result<PoseidonHash, PoseidonError> {
+    if vals.is_empty() || vals.len() > MAX_INPUTS {
+        return Err(PoseidonError::InvalidNumberOfInputs);
+    }
+
+    for val in vals {
+        if val.is_empty() {
+            return Err(PoseidonError::EmptyInput);
+        }
+        if val.len() > HASH_BYTES {
+            return Err(PoseidonError::InvalidInputLength);
+        }
+    }
+
+    let mut hash_result = core::mem::MaybeUninit::<[u8; HASH_BYTES]>::uninit();
+
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        crate::syscalls::sol_poseidon(
+            parameters as u64,
+            endianness as u64,
+            vals as *const _ as *const u8,
+            vals.len() as u64,
+            hash_result.as_mut_ptr() as *mut u8,
+        )
+    };
+
+    #[cfg(not(target_os = "solana"))]
+    let result = unsafe {
+        crate::host::sol_poseidon(
+            parameters as u64,
+            endianness as u64,
+            vals as *const _ as *const u8,
+            vals.len() as u64,
+            hash_result.as_mut_ptr() as *mut u8,
+        )
+    };
+
+    match result {
+        // SAFETY: The syscall writes the hash on success.
+        crate::SUCCESS => Ok(PoseidonHash(unsafe { hash_result.assume_init() })),
+        error => Err(PoseidonError::from(error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_validation() {
+        let input: &[u8] = &[1; HASH_BYTES];
+
+        assert_eq!(
+            hashv(Parameters::Bn254X5, Endianness::BigEndian, &[]),
+            Err(PoseidonError::InvalidNumberOfInputs)
+        );
+        assert_eq!(
+            hashv(
+                Parameters::Bn254X5,
+                Endianness::BigEndian,
+                &[input; MAX_INPUTS + 1]
+            ),
+            Err(PoseidonError::InvalidNumberOfInputs)
+        );
+        assert_eq!(
+            hashv(Parameters::Bn254X5, Endianness::LittleEndian, &[input, &[]]),
+            Err(PoseidonError::EmptyInput)
+        );
+        assert_eq!(
+            hash(
+                Parameters::Bn254X5,
+                Endianness::BigEndian,
+                &[1; HASH_BYTES + 1]
+            ),
+            Err(PoseidonError::InvalidInputLength)
+        );
+        assert_eq!(
+            PoseidonError::from(7),
+            PoseidonError::InputLargerThanModulus
+        );
+        assert_eq!(PoseidonError::from(42), PoseidonError::Unexpected);
+    }
+}
@@ -0,0 +1,236 @@
+//! Ed25519 signature verification precompile.
+//!
+//! The instruction data holds the number of signatures in its first byte, followed
+//! by a padding byte and the [`SignatureOffsets`] of each signature.
+
+use core::ops::Deref;
+
+use super::{
+    find_verified, resolve, resolve_slice, Precompile, PrecompileInstruction, SignatureOffsets,
+};
+use crate::{
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::instructions::{Instructions, IntrospectedInstruction},
+};
+
+/// Ed25519SigVerify111111111111111111111111111
+pub const ED25519_PROGRAM_ID: Pubkey = [
+    3, 125, 70, 214, 124, 147, 251, 190, 18, 249, 66, 143, 131, 141, 64, 255, 5, 112, 116, 73, 39,
+    244, 138, 100, 252, 202, 112, 68, 128, 0, 0, 0,
+];
+
+/// Length of an Ed25519 signature.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Length of an Ed25519 public key.
+pub const PUBKEY_LEN: usize = 32;
+
+/// Offset of the first [`SignatureOffsets`] in the instruction data.
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+
+/// A signature verified by the Ed25519 precompile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ed25519Signature<'a> {
+    /// The signature.
+    pub signature: &'a [u8; SIGNATURE_LEN],
+
+    /// The public key of the signer.
+    pub public_key: &'a Pubkey,
+
+    /// The signed message.
+    pub message: &'a [u8],
+}
+
+/// The Ed25519 precompile.
+///
+/// The signature, public key and message may be in the data of any instruction
+/// of the transaction.
+pub struct Ed25519;
+
+impl Precompile for Ed25519 {
+    const PROGRAM_ID: Pubkey = ED25519_PROGRAM_ID;
+
+    const SIGNATURE_OFFSETS_START: usize = SIGNATURE_OFFSETS_START;
+
+    type Offsets = SignatureOffsets;
+
+    type Signature<'a> = Ed25519Signature<'a>;
+
+    #[inline]
+    fn resolve<'a, T>(
+        instructions: &'a Instructions<T>,
+        precompile: &IntrospectedInstruction<'a>,
+        offsets: &SignatureOffsets,
+    ) -> Result<Ed25519Signature<'a>, ProgramError>
+    where
+        T: Deref<Target = [u8]>,
+    {
+        Ok(Ed25519Signature {
+            signature: resolve(
+                instructions,
+                precompile,
+                offsets.signature_instruction_index(),
+                offsets.signature_offset(),
+            )?,
+            public_key: resolve::<_, PUBKEY_LEN>(
+                instructions,
+                precompile,
+                offsets.public_key_instruction_index(),
+                offsets.public_key_offset(),
+            )?,
+            message: resolve_slice(
+                instructions,
+                precompile,
+                offsets.message_instruction_index(),
+                offsets.message_data_offset(),
+                offsets.message_data_size() as usize,
+            )?,
+        })
+    }
+}
+
+/// An Ed25519 precompile instruction.
+pub type Ed25519Instruction<'a> = PrecompileInstruction<'a, Ed25519>;
+
+/// Check that an Ed25519 precompile instruction of the transaction verified a
+/// signature of `message` by `public_key`.
+///
+/// Returns [`ProgramError::MissingRequiredSignature`] if no such signature was
+/// verified.
+pub fn verify_ed25519_was_checked<T>(
+    instructions: &Instructions<T>,
+    public_key: &Pubkey,
+    message: &[u8],
+) -> Result<(), ProgramError>
+where
+    T: Deref<Target = [u8]>,
+{
+    find_verified::<Ed25519, _>(instructions, |signature| {
+        signature.public_key == public_key && signature.message == message
+    })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::{
+        instruction::Instruction, precompiles::CURRENT_INSTRUCTION_INDEX,
+        sysvars::instructions::InstructionsBuilder,
+    };
+    use std::vec::Vec;
+
+    #[test]
+    fn test_signatures() {
+        let public_key = [7; PUBKEY_LEN];
+        let signature = [9; SIGNATURE_LEN];
+
+        // the signature and public key are in the precompile instruction, while the
+        // message is in the data of the instruction at index 1
+        let mut data = Vec::from([1, 0]);
+        let header_len = SIGNATURE_OFFSETS_START + SignatureOffsets::LEN;
+        let offsets = SignatureOffsets::new(
+            header_len as u16,
+            CURRENT_INSTRUCTION_INDEX,
+            (header_len + SIGNATURE_LEN) as u16,
+            CURRENT_INSTRUCTION_INDEX,
+            1,
+            5,
+            1,
+        );
+        data.extend_from_slice(offsets.as_bytes());
+        data.extend_from_slice(&signature);
+        data.extend_from_slice(&public_key);
+
+        let sysvar_data = InstructionsBuilder::new()
+            .instruction(&Instruction {
+                program_id: &ED25519_PROGRAM_ID,
+                data: &data,
+                accounts: &[],
+            })
+            .instruction(&Instruction {
+                program_id: &[1; 32],
+                data: b"_hello_",
+                accounts: &[],
+            })
+            .current_index(1)
+            .build();
+        let instructions = Instructions::new(sysvar_data.as_slice()).unwrap();
+
+        let precompile =
+            Ed25519Instruction::new(instructions.load_instruction_at(0).unwrap()).unwrap();
+        assert_eq!(precompile.num_signatures(), 1);
+        assert_eq!(
+            precompile.signature(&instructions, 0).unwrap(),
+            Ed25519Signature {
+                signature: &signature,
+                public_key: &public_key,
+                message: b"hello",
+            }
+        );
+        assert!(precompile.signature(&instructions, 1).is_err());
+
+        assert!(verify_ed25519_was_checked(&instructions, &public_key, b"hello").is_ok());
+        assert_eq!(
+            verify_ed25519_was_checked(&instructions, &public_key, b"hell"),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        assert_eq!(
+            verify_ed25519_was_checked(&instructions, &[8; PUBKEY_LEN], b"hello"),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+
+        assert_eq!(
+            Ed25519Instruction::new(instructions.load_instruction_at(1).unwrap()).err(),
+            Some(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_invalid_offsets() {
+        // two signatures, but only the offsets of one
+        let mut data = Vec::from([2, 0]);
+        data.extend_from_slice(&[0; SignatureOffsets::LEN]);
+
+        let sysvar_data = InstructionsBuilder::new()
+            .instruction(&Instruction {
+                program_id: &ED25519_PROGRAM_ID,
+                data: &data,
+                accounts: &[],
+            })
+            .build();
+        let instructions = Instructions::new(sysvar_data.as_slice()).unwrap();
+
+        assert_eq!(
+            Ed25519Instruction::new(instructions.load_instruction_at(0).unwrap()).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            verify_ed25519_was_checked(&instructions, &[0; PUBKEY_LEN], &[]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        // an offset outside of the referenced instruction data
+        let mut data = Vec::from([1, 0]);
+        let offsets = SignatureOffsets::new(1000, CURRENT_INSTRUCTION_INDEX, 0, 0, 0, 0, 5);
+        data.extend_from_slice(offsets.as_bytes());
+
+        let sysvar_data = InstructionsBuilder::new()
+            .instruction(&Instruction {
+                program_id: &ED25519_PROGRAM_ID,
+                data: &data,
+                accounts: &[],
+            })
+            .build();
+        let instructions = Instructions::new(sysvar_data.as_slice()).unwrap();
+        let precompile =
+            Ed25519Instruction::new(instructions.load_instruction_at(0).unwrap()).unwrap();
+
+        assert_eq!(
+            precompile.signature(&instructions, 0).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+    }
+}
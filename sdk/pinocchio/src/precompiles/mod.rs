@@ -0,0 +1,336 @@
+//! Parsers for the signature verification precompile instructions.
+//!
+//! Precompiles verify signatures before the transaction is executed, so the
+//! transaction fails if any of the signatures is invalid. A program can therefore
+//! require a signature by checking, through the [`Instructions`] sysvar, that a
+//! precompile instruction of the transaction verified it:
+//!
+//! ```ignore
+//! use pinocchio::{precompiles::ed25519::verify_ed25519_was_checked, sysvars::instructions::Instructions};
+//!
+//! let instructions = Instructions::try_from(instructions_sysvar)?;
+//! verify_ed25519_was_checked(&instructions, &authority, message)?;
+//! ```
+//!
+//! The signature, public key and message of each verification are referenced by
+//! offsets into the data of any instruction of the transaction, which are
+//! resolved by the parsers of each precompile.
+
+use core::ops::Deref;
+
+use crate::{
+    pod::Pod,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::instructions::{Instructions, IntrospectedInstruction},
+};
+
+pub mod ed25519;
+pub mod secp256k1;
+pub mod secp256r1;
+
+/// Instruction index referring to the precompile instruction itself.
+///
+/// This is only supported by the Ed25519 and Secp256r1 precompiles.
+pub const CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
+
+/// Offsets of a signature verification of the Ed25519 and Secp256r1 precompiles.
+///
+/// Each field is a little-endian `u16`, so the values are accessed through methods.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SignatureOffsets {
+    /// Offset to the signature.
+    signature_offset: [u8; 2],
+
+    /// Index of the instruction holding the signature.
+    signature_instruction_index: [u8; 2],
+
+    /// Offset to the public key.
+    public_key_offset: [u8; 2],
+
+    /// Index of the instruction holding the public key.
+    public_key_instruction_index: [u8; 2],
+
+    /// Offset to the message.
+    message_data_offset: [u8; 2],
+
+    /// Length of the message.
+    message_data_size: [u8; 2],
+
+    /// Index of the instruction holding the message.
+    message_instruction_index: [u8; 2],
+}
+
+const _: () = assert!(core::mem::size_of::<SignatureOffsets>() == SignatureOffsets::LEN);
+
+// SAFETY: `SignatureOffsets` is `repr(C)` and only holds byte arrays.
+unsafe impl Pod for SignatureOffsets {}
+
+impl SignatureOffsets {
+    /// The length of a `SignatureOffsets`.
+    pub const LEN: usize = 7 * 2;
+
+    /// Create a new `SignatureOffsets`.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn new(
+        signature_offset: u16,
+        signature_instruction_index: u16,
+        public_key_offset: u16,
+        public_key_instruction_index: u16,
+        message_data_offset: u16,
+        message_data_size: u16,
+        message_instruction_index: u16,
+    ) -> Self {
+        Self {
+            signature_offset: signature_offset.to_le_bytes(),
+            signature_instruction_index: signature_instruction_index.to_le_bytes(),
+            public_key_offset: public_key_offset.to_le_bytes(),
+            public_key_instruction_index: public_key_instruction_index.to_le_bytes(),
+            message_data_offset: message_data_offset.to_le_bytes(),
+            message_data_size: message_data_size.to_le_bytes(),
+            message_instruction_index: message_instruction_index.to_le_bytes(),
+        }
+    }
+
+    /// Return the bytes of the offsets, as encoded in the precompile instruction
+    /// data.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8; Self::LEN] {
+        // SAFETY: The type has alignment 1, no padding and a size of `LEN` bytes.
+        unsafe { &*(self as *const Self as *const [u8; Self::LEN]) }
+    }
+
+    /// Offset to the signature.
+    #[inline(always)]
+    pub fn signature_offset(&self) -> u16 {
+        u16::from_le_bytes(self.signature_offset)
+    }
+
+    /// Index of the instruction holding the signature.
+    #[inline(always)]
+    pub fn signature_instruction_index(&self) -> u16 {
+        u16::from_le_bytes(self.signature_instruction_index)
+    }
+
+    /// Offset to the public key.
+    #[inline(always)]
+    pub fn public_key_offset(&self) -> u16 {
+        u16::from_le_bytes(self.public_key_offset)
+    }
+
+    /// Index of the instruction holding the public key.
+    #[inline(always)]
+    pub fn public_key_instruction_index(&self) -> u16 {
+        u16::from_le_bytes(self.public_key_instruction_index)
+    }
+
+    /// Offset to the message.
+    #[inline(always)]
+    pub fn message_data_offset(&self) -> u16 {
+        u16::from_le_bytes(self.message_data_offset)
+    }
+
+    /// Length of the message.
+    #[inline(always)]
+    pub fn message_data_size(&self) -> u16 {
+        u16::from_le_bytes(self.message_data_size)
+    }
+
+    /// Index of the instruction holding the message.
+    #[inline(always)]
+    pub fn message_instruction_index(&self) -> u16 {
+        u16::from_le_bytes(self.message_instruction_index)
+    }
+}
+
+/// A signature verification precompile.
+pub trait Precompile {
+    /// The ID of the precompile program.
+    const PROGRAM_ID: Pubkey;
+
+    /// Offset of the first signature offsets in the instruction data.
+    const SIGNATURE_OFFSETS_START: usize;
+
+    /// The offsets of a signature verification.
+    type Offsets: Pod;
+
+    /// A signature verified by the precompile.
+    type Signature<'a>;
+
+    /// Return the signature referenced by `offsets`, resolving them against the
+    /// instructions of the transaction.
+    fn resolve<'a, T>(
+        instructions: &'a Instructions<T>,
+        precompile: &IntrospectedInstruction<'a>,
+        offsets: &Self::Offsets,
+    ) -> Result<Self::Signature<'a>, ProgramError>
+    where
+        T: Deref<Target = [u8]>;
+}
+
+/// A precompile instruction.
+pub struct PrecompileInstruction<'a, P: Precompile> {
+    /// The introspected precompile instruction.
+    instruction: IntrospectedInstruction<'a>,
+
+    /// The offsets of each signature.
+    offsets: &'a [P::Offsets],
+}
+
+impl<'a, P: Precompile> PrecompileInstruction<'a, P> {
+    /// Parse a precompile instruction.
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the instruction does not
+    /// invoke the precompile, and [`ProgramError::InvalidInstructionData`] if the
+    /// instruction data does not hold the offsets of all signatures.
+    #[inline]
+    pub fn new(instruction: IntrospectedInstruction<'a>) -> Result<Self, ProgramError> {
+        if instruction.get_program_id() != &P::PROGRAM_ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let offsets = parse_offsets(
+            instruction.get_instruction_data(),
+            P::SIGNATURE_OFFSETS_START,
+        )?;
+
+        Ok(Self {
+            instruction,
+            offsets,
+        })
+    }
+
+    /// The introspected precompile instruction.
+    #[inline(always)]
+    pub fn instruction(&self) -> &IntrospectedInstruction<'a> {
+        &self.instruction
+    }
+
+    /// Return the number of signatures verified by the instruction.
+    #[inline(always)]
+    pub fn num_signatures(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Return the offsets of each signature.
+    #[inline(always)]
+    pub fn offsets(&self) -> &'a [P::Offsets] {
+        self.offsets
+    }
+
+    /// Return the signature at the given index, resolving its offsets.
+    #[inline]
+    pub fn signature<T>(
+        &self,
+        instructions: &'a Instructions<T>,
+        index: usize,
+    ) -> Result<P::Signature<'a>, ProgramError>
+    where
+        T: Deref<Target = [u8]>,
+    {
+        let offsets = self
+            .offsets
+            .get(index)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        P::resolve(instructions, &self.instruction, offsets)
+    }
+}
+
+/// Check that a precompile instruction of the transaction verified a signature
+/// for which `matches` returns `true`.
+///
+/// Returns [`ProgramError::MissingRequiredSignature`] if no such signature was
+/// verified.
+fn find_verified<P, T>(
+    instructions: &Instructions<T>,
+    matches: impl Fn(&P::Signature<'_>) -> bool,
+) -> Result<(), ProgramError>
+where
+    P: Precompile,
+    T: Deref<Target = [u8]>,
+{
+    for instruction in instructions {
+        if instruction.get_program_id() != &P::PROGRAM_ID {
+            continue;
+        }
+
+        let precompile = PrecompileInstruction::<P>::new(instruction)?;
+
+        for index in 0..precompile.num_signatures() {
+            if matches(&precompile.signature(instructions, index)?) {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(ProgramError::MissingRequiredSignature)
+}
+
+/// Return the signature offsets of a precompile instruction data, where the
+/// number of signatures is stored in the first byte and the offsets start at
+/// `start`.
+#[inline(always)]
+fn parse_offsets<O: Pod>(data: &[u8], start: usize) -> Result<&[O], ProgramError> {
+    let num_signatures = *data.first().ok_or(ProgramError::InvalidInstructionData)? as usize;
+
+    if data.len() < start + num_signatures * core::mem::size_of::<O>() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // SAFETY: `O` is `Pod` and `data` holds `num_signatures` values after `start`.
+    Ok(
+        unsafe {
+            core::slice::from_raw_parts(data.as_ptr().add(start) as *const O, num_signatures)
+        },
+    )
+}
+
+/// Return the `N` bytes at `offset` of the data of the instruction at
+/// `instruction_index`.
+///
+/// The [`CURRENT_INSTRUCTION_INDEX`] refers to the `precompile` instruction.
+#[inline(always)]
+fn resolve<'a, T, const N: usize>(
+    instructions: &'a Instructions<T>,
+    precompile: &IntrospectedInstruction<'a>,
+    instruction_index: u16,
+    offset: u16,
+) -> Result<&'a [u8; N], ProgramError>
+where
+    T: Deref<Target = [u8]>,
+{
+    let bytes = resolve_slice(instructions, precompile, instruction_index, offset, N)?;
+    // SAFETY: `bytes` is `N` bytes long.
+    Ok(unsafe { &*(bytes.as_ptr() as *const [u8; N]) })
+}
+
+/// Return the `len` bytes at `offset` of the data of the instruction at
+/// `instruction_index`.
+///
+/// The [`CURRENT_INSTRUCTION_INDEX`] refers to the `precompile` instruction.
+#[inline]
+fn resolve_slice<'a, T>(
+    instructions: &'a Instructions<T>,
+    precompile: &IntrospectedInstruction<'a>,
+    instruction_index: u16,
+    offset: u16,
+    len: usize,
+) -> Result<&'a [u8], ProgramError>
+where
+    T: Deref<Target = [u8]>,
+{
+    let data = if instruction_index == CURRENT_INSTRUCTION_INDEX {
+        precompile.get_instruction_data()
+    } else {
+        instructions
+            .load_instruction_at(instruction_index as usize)?
+            .get_instruction_data()
+    };
+
+    let offset = offset as usize;
+    data.get(offset..offset + len)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
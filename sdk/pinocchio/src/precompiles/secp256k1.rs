@@ -0,0 +1,322 @@
+//! Secp256k1 signature verification precompile.
+//!
+//! The precompile recovers the Ethereum address of the signer of the Keccak-256
+//! hash of a message. The instruction data holds the number of signatures in its
+//! first byte, followed by the [`Secp256k1SignatureOffsets`] of each signature.
+//!
+//! Unlike the other precompiles, instruction indices always refer to an
+//! instruction of the transaction.
+
+use core::ops::Deref;
+
+use super::{find_verified, resolve, resolve_slice, Precompile, PrecompileInstruction};
+use crate::{
+    pod::Pod,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::instructions::{Instructions, IntrospectedInstruction},
+};
+
+/// KeccakSecp256k11111111111111111111111111111
+pub const SECP256K1_PROGRAM_ID: Pubkey = [
+    4, 198, 252, 32, 240, 80, 204, 240, 85, 132, 215, 33, 28, 159, 140, 245, 158, 193, 71, 133,
+    187, 22, 106, 30, 40, 48, 232, 18, 32, 0, 0, 0,
+];
+
+/// Length of a Secp256k1 signature, without the recovery id.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Length of an Ethereum address.
+pub const ETH_ADDRESS_LEN: usize = 20;
+
+/// Offset of the first [`Secp256k1SignatureOffsets`] in the instruction data.
+pub const SIGNATURE_OFFSETS_START: usize = 1;
+
+/// Offsets of a signature verification of the Secp256k1 precompile.
+///
+/// Offsets are little-endian `u16` values, so the values are accessed through
+/// methods.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Secp256k1SignatureOffsets {
+    /// Offset to the signature, followed by the recovery id.
+    signature_offset: [u8; 2],
+
+    /// Index of the instruction holding the signature.
+    signature_instruction_index: u8,
+
+    /// Offset to the Ethereum address.
+    eth_address_offset: [u8; 2],
+
+    /// Index of the instruction holding the Ethereum address.
+    eth_address_instruction_index: u8,
+
+    /// Offset to the message.
+    message_data_offset: [u8; 2],
+
+    /// Length of the message.
+    message_data_size: [u8; 2],
+
+    /// Index of the instruction holding the message.
+    message_instruction_index: u8,
+}
+
+const _: () =
+    assert!(core::mem::size_of::<Secp256k1SignatureOffsets>() == Secp256k1SignatureOffsets::LEN);
+
+// SAFETY: `Secp256k1SignatureOffsets` is `repr(C)` and only holds bytes and byte
+// arrays.
+unsafe impl Pod for Secp256k1SignatureOffsets {}
+
+impl Secp256k1SignatureOffsets {
+    /// The length of a `Secp256k1SignatureOffsets`.
+    pub const LEN: usize = 4 * 2 + 3;
+
+    /// Create a new `Secp256k1SignatureOffsets`.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn new(
+        signature_offset: u16,
+        signature_instruction_index: u8,
+        eth_address_offset: u16,
+        eth_address_instruction_index: u8,
+        message_data_offset: u16,
+        message_data_size: u16,
+        message_instruction_index: u8,
+    ) -> Self {
+        Self {
+            signature_offset: signature_offset.to_le_bytes(),
+            signature_instruction_index,
+            eth_address_offset: eth_address_offset.to_le_bytes(),
+            eth_address_instruction_index,
+            message_data_offset: message_data_offset.to_le_bytes(),
+            message_data_size: message_data_size.to_le_bytes(),
+            message_instruction_index,
+        }
+    }
+
+    /// Return the bytes of the offsets, as encoded in the precompile instruction
+    /// data.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8; Self::LEN] {
+        // SAFETY: The type has alignment 1, no padding and a size of `LEN` bytes.
+        unsafe { &*(self as *const Self as *const [u8; Self::LEN]) }
+    }
+
+    /// Offset to the signature, followed by the recovery id.
+    #[inline(always)]
+    pub fn signature_offset(&self) -> u16 {
+        u16::from_le_bytes(self.signature_offset)
+    }
+
+    /// Index of the instruction holding the signature.
+    #[inline(always)]
+    pub fn signature_instruction_index(&self) -> u8 {
+        self.signature_instruction_index
+    }
+
+    /// Offset to the Ethereum address.
+    #[inline(always)]
+    pub fn eth_address_offset(&self) -> u16 {
+        u16::from_le_bytes(self.eth_address_offset)
+    }
+
+    /// Index of the instruction holding the Ethereum address.
+    #[inline(always)]
+    pub fn eth_address_instruction_index(&self) -> u8 {
+        self.eth_address_instruction_index
+    }
+
+    /// Offset to the message.
+    #[inline(always)]
+    pub fn message_data_offset(&self) -> u16 {
+        u16::from_le_bytes(self.message_data_offset)
+    }
+
+    /// Length of the message.
+    #[inline(always)]
+    pub fn message_data_size(&self) -> u16 {
+        u16::from_le_bytes(self.message_data_size)
+    }
+
+    /// Index of the instruction holding the message.
+    #[inline(always)]
+    pub fn message_instruction_index(&self) -> u8 {
+        self.message_instruction_index
+    }
+}
+
+/// A signature verified by the Secp256k1 precompile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Secp256k1Signature<'a> {
+    /// The signature.
+    pub signature: &'a [u8; SIGNATURE_LEN],
+
+    /// The recovery id of the signature.
+    pub recovery_id: u8,
+
+    /// The Ethereum address of the signer.
+    pub eth_address: &'a [u8; ETH_ADDRESS_LEN],
+
+    /// The signed message.
+    pub message: &'a [u8],
+}
+
+/// The Secp256k1 precompile.
+///
+/// The signature, Ethereum address and message may be in the data of any
+/// instruction of the transaction.
+pub struct Secp256k1;
+
+impl Precompile for Secp256k1 {
+    const PROGRAM_ID: Pubkey = SECP256K1_PROGRAM_ID;
+
+    const SIGNATURE_OFFSETS_START: usize = SIGNATURE_OFFSETS_START;
+
+    type Offsets = Secp256k1SignatureOffsets;
+
+    type Signature<'a> = Secp256k1Signature<'a>;
+
+    #[inline]
+    fn resolve<'a, T>(
+        instructions: &'a Instructions<T>,
+        precompile: &IntrospectedInstruction<'a>,
+        offsets: &Secp256k1SignatureOffsets,
+    ) -> Result<Secp256k1Signature<'a>, ProgramError>
+    where
+        T: Deref<Target = [u8]>,
+    {
+        let signature = resolve::<_, { SIGNATURE_LEN + 1 }>(
+            instructions,
+            precompile,
+            offsets.signature_instruction_index() as u16,
+            offsets.signature_offset(),
+        )?;
+
+        Ok(Secp256k1Signature {
+            // SAFETY: `signature` is `SIGNATURE_LEN + 1` bytes long.
+            signature: unsafe { &*(signature.as_ptr() as *const [u8; SIGNATURE_LEN]) },
+            recovery_id: signature[SIGNATURE_LEN],
+            eth_address: resolve(
+                instructions,
+                precompile,
+                offsets.eth_address_instruction_index() as u16,
+                offsets.eth_address_offset(),
+            )?,
+            message: resolve_slice(
+                instructions,
+                precompile,
+                offsets.message_instruction_index() as u16,
+                offsets.message_data_offset(),
+                offsets.message_data_size() as usize,
+            )?,
+        })
+    }
+}
+
+/// A Secp256k1 precompile instruction.
+pub type Secp256k1Instruction<'a> = PrecompileInstruction<'a, Secp256k1>;
+
+/// Check that a Secp256k1 precompile instruction of the transaction verified a
+/// signature of `message` by the signer of the given Ethereum address.
+///
+/// Returns [`ProgramError::MissingRequiredSignature`] if no such signature was
+/// verified.
+pub fn verify_secp256k1_was_checked<T>(
+    instructions: &Instructions<T>,
+    eth_address: &[u8; ETH_ADDRESS_LEN],
+    message: &[u8],
+) -> Result<(), ProgramError>
+where
+    T: Deref<Target = [u8]>,
+{
+    find_verified::<Secp256k1, _>(instructions, |signature| {
+        signature.eth_address == eth_address && signature.message == message
+    })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::{instruction::Instruction, sysvars::instructions::InstructionsBuilder};
+    use std::vec::Vec;
+
+    #[test]
+    fn test_signatures() {
+        let eth_address = [7; ETH_ADDRESS_LEN];
+        let signature = [9; SIGNATURE_LEN];
+
+        // the signature and address are in the precompile instruction at index 0,
+        // while the message is in the data of the instruction at index 1
+        let mut data = Vec::from([1]);
+        let header_len = SIGNATURE_OFFSETS_START + Secp256k1SignatureOffsets::LEN;
+        let offsets = Secp256k1SignatureOffsets::new(
+            header_len as u16,
+            0,
+            (header_len + SIGNATURE_LEN + 1) as u16,
+            0,
+            0,
+            5,
+            1,
+        );
+        data.extend_from_slice(offsets.as_bytes());
+        data.extend_from_slice(&signature);
+        data.push(1);
+        data.extend_from_slice(&eth_address);
+
+        let sysvar_data = InstructionsBuilder::new()
+            .instruction(&Instruction {
+                program_id: &SECP256K1_PROGRAM_ID,
+                data: &data,
+                accounts: &[],
+            })
+            .instruction(&Instruction {
+                program_id: &[1; 32],
+                data: b"hello",
+                accounts: &[],
+            })
+            .current_index(1)
+            .build();
+        let instructions = Instructions::new(sysvar_data.as_slice()).unwrap();
+
+        let precompile =
+            Secp256k1Instruction::new(instructions.load_instruction_at(0).unwrap()).unwrap();
+        assert_eq!(precompile.num_signatures(), 1);
+        assert_eq!(
+            precompile.signature(&instructions, 0).unwrap(),
+            Secp256k1Signature {
+                signature: &signature,
+                recovery_id: 1,
+                eth_address: &eth_address,
+                message: b"hello",
+            }
+        );
+
+        assert!(verify_secp256k1_was_checked(&instructions, &eth_address, b"hello").is_ok());
+        assert_eq!(
+            verify_secp256k1_was_checked(&instructions, &[8; ETH_ADDRESS_LEN], b"hello"),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+
+        // the instruction index refers to a missing instruction
+        let mut data = Vec::from([1]);
+        let offsets = Secp256k1SignatureOffsets::new(0, 5, 0, 0, 0, 0, 0);
+        data.extend_from_slice(offsets.as_bytes());
+
+        let sysvar_data = InstructionsBuilder::new()
+            .instruction(&Instruction {
+                program_id: &SECP256K1_PROGRAM_ID,
+                data: &data,
+                accounts: &[],
+            })
+            .build();
+        let instructions = Instructions::new(sysvar_data.as_slice()).unwrap();
+
+        assert_eq!(
+            verify_secp256k1_was_checked(&instructions, &eth_address, b"hello"),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
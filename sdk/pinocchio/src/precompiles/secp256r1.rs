@@ -0,0 +1,190 @@
+//! Secp256r1 (P-256) signature verification precompile.
+//!
+//! The instruction data holds the number of signatures in its first byte, followed
+//! by a padding byte and the [`SignatureOffsets`] of each signature.
+
+use core::ops::Deref;
+
+use super::{
+    find_verified, resolve, resolve_slice, Precompile, PrecompileInstruction, SignatureOffsets,
+};
+use crate::{
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::instructions::{Instructions, IntrospectedInstruction},
+};
+
+/// Secp256r1SigVerify1111111111111111111111111
+pub const SECP256R1_PROGRAM_ID: Pubkey = [
+    6, 146, 13, 236, 47, 234, 113, 181, 183, 35, 129, 77, 116, 45, 169, 3, 28, 131, 231, 95, 219,
+    121, 93, 86, 142, 117, 71, 128, 32, 0, 0, 0,
+];
+
+/// Length of a Secp256r1 signature.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Length of a compressed Secp256r1 public key.
+pub const PUBKEY_LEN: usize = 33;
+
+/// Offset of the first [`SignatureOffsets`] in the instruction data.
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+
+/// A signature verified by the Secp256r1 precompile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Secp256r1Signature<'a> {
+    /// The signature.
+    pub signature: &'a [u8; SIGNATURE_LEN],
+
+    /// The compressed public key of the signer.
+    pub public_key: &'a [u8; PUBKEY_LEN],
+
+    /// The signed message.
+    pub message: &'a [u8],
+}
+
+/// The Secp256r1 precompile.
+///
+/// The signature, public key and message may be in the data of any instruction
+/// of the transaction.
+pub struct Secp256r1;
+
+impl Precompile for Secp256r1 {
+    const PROGRAM_ID: Pubkey = SECP256R1_PROGRAM_ID;
+
+    const SIGNATURE_OFFSETS_START: usize = SIGNATURE_OFFSETS_START;
+
+    type Offsets = SignatureOffsets;
+
+    type Signature<'a> = Secp256r1Signature<'a>;
+
+    #[inline]
+    fn resolve<'a, T>(
+        instructions: &'a Instructions<T>,
+        precompile: &IntrospectedInstruction<'a>,
+        offsets: &SignatureOffsets,
+    ) -> Result<Secp256r1Signature<'a>, ProgramError>
+    where
+        T: Deref<Target = [u8]>,
+    {
+        Ok(Secp256r1Signature {
+            signature: resolve(
+                instructions,
+                precompile,
+                offsets.signature_instruction_index(),
+                offsets.signature_offset(),
+            )?,
+            public_key: resolve(
+                instructions,
+                precompile,
+                offsets.public_key_instruction_index(),
+                offsets.public_key_offset(),
+            )?,
+            message: resolve_slice(
+                instructions,
+                precompile,
+                offsets.message_instruction_index(),
+                offsets.message_data_offset(),
+                offsets.message_data_size() as usize,
+            )?,
+        })
+    }
+}
+
+/// A Secp256r1 precompile instruction.
+pub type Secp256r1Instruction<'a> = PrecompileInstruction<'a, Secp256r1>;
+
+/// Check that a Secp256r1 precompile instruction of the transaction verified a
+/// signature of `message` by `public_key`.
+///
+/// Returns [`ProgramError::MissingRequiredSignature`] if no such signature was
+/// verified.
+pub fn verify_secp256r1_was_checked<T>(
+    instructions: &Instructions<T>,
+    public_key: &[u8; PUBKEY_LEN],
+    message: &[u8],
+) -> Result<(), ProgramError>
+where
+    T: Deref<Target = [u8]>,
+{
+    find_verified::<Secp256r1, _>(instructions, |signature| {
+        signature.public_key == public_key && signature.message == message
+    })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::{
+        instruction::Instruction, precompiles::CURRENT_INSTRUCTION_INDEX,
+        sysvars::instructions::InstructionsBuilder,
+    };
+    use std::vec::Vec;
+
+    #[test]
+    fn test_signatures() {
+        let public_key = [7; PUBKEY_LEN];
+        let signature = [9; SIGNATURE_LEN];
+
+        // the signature and public key are in the precompile instruction, while the
+        // message is in the data of the instruction at index 1
+        let mut data = Vec::from([1, 0]);
+        let header_len = SIGNATURE_OFFSETS_START + SignatureOffsets::LEN;
+        let offsets = SignatureOffsets::new(
+            header_len as u16,
+            CURRENT_INSTRUCTION_INDEX,
+            (header_len + SIGNATURE_LEN) as u16,
+            CURRENT_INSTRUCTION_INDEX,
+            1,
+            5,
+            1,
+        );
+        data.extend_from_slice(offsets.as_bytes());
+        data.extend_from_slice(&signature);
+        data.extend_from_slice(&public_key);
+
+        let sysvar_data = InstructionsBuilder::new()
+            .instruction(&Instruction {
+                program_id: &SECP256R1_PROGRAM_ID,
+                data: &data,
+                accounts: &[],
+            })
+            .instruction(&Instruction {
+                program_id: &[1; 32],
+                data: b"_hello_",
+                accounts: &[],
+            })
+            .current_index(1)
+            .build();
+        let instructions = Instructions::new(sysvar_data.as_slice()).unwrap();
+
+        let precompile =
+            Secp256r1Instruction::new(instructions.load_instruction_at(0).unwrap()).unwrap();
+        assert_eq!(precompile.num_signatures(), 1);
+        assert_eq!(
+            precompile.signature(&instructions, 0).unwrap(),
+            Secp256r1Signature {
+                signature: &signature,
+                public_key: &public_key,
+                message: b"hello",
+            }
+        );
+        assert!(precompile.signature(&instructions, 1).is_err());
+
+        assert!(verify_secp256r1_was_checked(&instructions, &public_key, b"hello").is_ok());
+        assert_eq!(
+            verify_secp256r1_was_checked(&instructions, &public_key, b"hell"),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        assert_eq!(
+            verify_secp256r1_was_checked(&instructions, &[8; PUBKEY_LEN], b"hello"),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+
+        assert_eq!(
+            Secp256r1Instruction::new(instructions.load_instruction_at(1).unwrap()).err(),
+            Some(ProgramError::IncorrectProgramId)
+        );
+    }
+}
@@ -11,6 +11,9 @@ pub const MAX_SEED_LEN: usize = 32;
 /// Maximum number of seeds.
 pub const MAX_SEEDS: usize = 16;
 
+/// Marker appended to the seeds when deriving a program address.
+pub const PDA_MARKER: &[u8; 21] = b"ProgramDerivedAddress";
+
 /// The address of a [Solana account][account].
 ///
 /// [account]: https://solana.com/docs/core/accounts
@@ -25,7 +28,9 @@ pub fn log(pubkey: &Pubkey) {
     };
 
     #[cfg(not(target_os = "solana"))]
-    core::hint::black_box(pubkey);
+    unsafe {
+        crate::host::sol_log_pubkey(pubkey as *const _ as *const u8)
+    };
 }
 
 /// Find a valid [program derived address][pda] and its corresponding bump seed.
@@ -118,31 +123,36 @@ pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8
 /// [`find_program_address`]: #find_program_address
 #[inline]
 pub fn try_find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    let mut bytes = core::mem::MaybeUninit::<[u8; PUBKEY_BYTES]>::uninit();
+    let mut bump_seed = u8::MAX;
+
     #[cfg(target_os = "solana")]
-    {
-        let mut bytes = core::mem::MaybeUninit::<[u8; PUBKEY_BYTES]>::uninit();
-        let mut bump_seed = u8::MAX;
-
-        let result = unsafe {
-            crate::syscalls::sol_try_find_program_address(
-                seeds as *const _ as *const u8,
-                seeds.len() as u64,
-                program_id as *const _,
-                bytes.as_mut_ptr() as *mut _,
-                &mut bump_seed as *mut _,
-            )
-        };
-        match result {
-            // SAFETY: The syscall has initialized the bytes.
-            crate::SUCCESS => Some((unsafe { bytes.assume_init() }, bump_seed)),
-            _ => None,
-        }
-    }
+    let result = unsafe {
+        crate::syscalls::sol_try_find_program_address(
+            seeds as *const _ as *const u8,
+            seeds.len() as u64,
+            program_id as *const _,
+            bytes.as_mut_ptr() as *mut _,
+            &mut bump_seed as *mut _,
+        )
+    };
 
+    // Off-chain, the address is derived using a pure Rust implementation.
     #[cfg(not(target_os = "solana"))]
-    {
-        core::hint::black_box((seeds, program_id));
-        None
+    let result = unsafe {
+        crate::host::sol_try_find_program_address(
+            seeds as *const _ as *const u8,
+            seeds.len() as u64,
+            program_id as *const _,
+            bytes.as_mut_ptr() as *mut _,
+            &mut bump_seed as *mut _,
+        )
+    };
+
+    match result {
+        // SAFETY: The syscall has initialized the bytes.
+        crate::SUCCESS => Some((unsafe { bytes.assume_init() }, bump_seed)),
+        _ => None,
     }
 }
 
@@ -171,31 +181,34 @@ pub fn create_program_address(
     seeds: &[&[u8]],
     program_id: &Pubkey,
 ) -> Result<Pubkey, ProgramError> {
+    let mut bytes = core::mem::MaybeUninit::<[u8; PUBKEY_BYTES]>::uninit();
+
     // Call via a system call to perform the calculation
     #[cfg(target_os = "solana")]
-    {
-        let mut bytes = core::mem::MaybeUninit::<[u8; PUBKEY_BYTES]>::uninit();
-
-        let result = unsafe {
-            crate::syscalls::sol_create_program_address(
-                seeds as *const _ as *const u8,
-                seeds.len() as u64,
-                program_id as *const _ as *const u8,
-                bytes.as_mut_ptr() as *mut u8,
-            )
-        };
-
-        match result {
-            // SAFETY: The syscall has initialized the bytes.
-            crate::SUCCESS => Ok(unsafe { bytes.assume_init() }),
-            _ => Err(result.into()),
-        }
-    }
+    let result = unsafe {
+        crate::syscalls::sol_create_program_address(
+            seeds as *const _ as *const u8,
+            seeds.len() as u64,
+            program_id as *const _ as *const u8,
+            bytes.as_mut_ptr() as *mut u8,
+        )
+    };
 
+    // Off-chain, the address is derived using a pure Rust implementation.
     #[cfg(not(target_os = "solana"))]
-    {
-        core::hint::black_box((seeds, program_id));
-        panic!("create_program_address is only available on target `solana`")
+    let result = unsafe {
+        crate::host::sol_create_program_address(
+            seeds as *const _ as *const u8,
+            seeds.len() as u64,
+            program_id as *const _ as *const u8,
+            bytes.as_mut_ptr() as *mut u8,
+        )
+    };
+
+    match result {
+        // SAFETY: The syscall has initialized the bytes.
+        crate::SUCCESS => Ok(unsafe { bytes.assume_init() }),
+        _ => Err(result.into()),
     }
 }
 
@@ -232,3 +245,97 @@ pub fn checked_create_program_address(
 
     create_program_address(seeds, program_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `BPFLoaderUpgradeab1e11111111111111111111111`
+    const PROGRAM_ID: Pubkey = [
+        2, 168, 246, 145, 78, 136, 161, 176, 226, 16, 21, 62, 247, 99, 174, 43, 0, 194, 185, 61,
+        22, 193, 36, 210, 192, 83, 122, 16, 4, 128, 0, 0,
+    ];
+
+    /// `SeedPubey1111111111111111111111111111111111`
+    const SEED_PUBKEY: Pubkey = [
+        6, 146, 22, 253, 104, 104, 101, 33, 142, 39, 92, 160, 151, 134, 69, 132, 93, 139, 182, 26,
+        37, 18, 113, 241, 23, 63, 138, 200, 0, 0, 0, 0,
+    ];
+
+    #[test]
+    fn test_create_program_address() {
+        let exceeded_seed: &[u8] = &[127; MAX_SEED_LEN + 1];
+        let max_seed: &[u8] = &[0; MAX_SEED_LEN];
+
+        assert_eq!(
+            create_program_address(&[exceeded_seed], &PROGRAM_ID),
+            Err(ProgramError::MaxSeedLengthExceeded)
+        );
+        assert_eq!(
+            create_program_address(&[b"short_seed", exceeded_seed], &PROGRAM_ID),
+            Err(ProgramError::MaxSeedLengthExceeded)
+        );
+        assert!(create_program_address(&[max_seed], &PROGRAM_ID).is_ok());
+        assert_eq!(
+            create_program_address(&[&[0u8] as &[u8]; MAX_SEEDS + 1], &PROGRAM_ID),
+            Err(ProgramError::MaxSeedLengthExceeded)
+        );
+
+        // BwqrghZA2htAcqq8dzP1WDAhTXYTYWj7CHxF5j7TDBAe
+        assert_eq!(
+            create_program_address(&[b"", &[1]], &PROGRAM_ID),
+            Ok([
+                162, 161, 121, 174, 143, 213, 46, 144, 219, 132, 223, 53, 51, 70, 157, 140, 80, 36,
+                65, 213, 187, 193, 131, 45, 141, 34, 254, 55, 112, 3, 97, 87
+            ])
+        );
+        // 13yWmRpaTR4r5nAktwLqMpRNr28tnVUZw26rTvPSSB19
+        assert_eq!(
+            create_program_address(&["☉".as_bytes(), &[0]], &PROGRAM_ID),
+            Ok([
+                0, 195, 15, 177, 173, 63, 123, 59, 165, 151, 87, 229, 211, 60, 243, 17, 19, 21, 3,
+                248, 154, 163, 253, 147, 106, 54, 82, 53, 122, 73, 86, 232
+            ])
+        );
+        // 2fnQrngrQT4SeLcdToJAD96phoEjNL2man2kfRLCASVk
+        assert_eq!(
+            create_program_address(&[b"Talking", b"Squirrels"], &PROGRAM_ID),
+            Ok([
+                24, 203, 26, 189, 68, 5, 186, 249, 17, 157, 17, 228, 105, 119, 194, 16, 6, 61, 106,
+                141, 250, 199, 103, 239, 103, 28, 247, 232, 96, 150, 138, 255
+            ])
+        );
+        // 976ymqVnfE32QFe6NfGDctSvVa36LWnvYxhU6G2232YL
+        assert_eq!(
+            create_program_address(&[&SEED_PUBKEY, &[1]], &PROGRAM_ID),
+            Ok([
+                120, 109, 189, 149, 211, 10, 43, 134, 71, 231, 208, 110, 135, 244, 9, 243, 160,
+                175, 231, 114, 93, 108, 16, 8, 196, 13, 163, 112, 39, 122, 204, 125
+            ])
+        );
+        assert_ne!(
+            create_program_address(&[b"Talking", b"Squirrels"], &PROGRAM_ID),
+            create_program_address(&[b"Talking"], &PROGRAM_ID)
+        );
+    }
+
+    #[test]
+    fn test_find_program_address() {
+        for seed in [b"Lil'".as_ref(), b"Bits", &SEED_PUBKEY] {
+            let (address, bump_seed) = find_program_address(&[seed], &PROGRAM_ID);
+            assert_eq!(
+                address,
+                create_program_address(&[seed, &[bump_seed]], &PROGRAM_ID).unwrap()
+            );
+            // all higher bump seeds result in an address on the curve
+            for bump in (bump_seed..=u8::MAX).skip(1) {
+                assert_eq!(
+                    create_program_address(&[seed, &[bump]], &PROGRAM_ID),
+                    Err(ProgramError::InvalidSeeds)
+                );
+            }
+        }
+
+        assert!(try_find_program_address(&[&[0u8] as &[u8]; MAX_SEEDS], &PROGRAM_ID).is_none());
+    }
+}
@@ -0,0 +1,62 @@
+//! Stake delegated to vote accounts in the current epoch.
+//!
+//! The stake is read through the `sol_get_epoch_stake` syscall, so the vote
+//! accounts are not required. This allows programs to weight votes or check a
+//! quorum by the stake of validators.
+
+use crate::pubkey::Pubkey;
+
+/// Return the total active stake of the current epoch, in lamports.
+#[inline(always)]
+pub fn get_epoch_total_stake() -> u64 {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        crate::syscalls::sol_get_epoch_stake(core::ptr::null())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    unsafe {
+        crate::host::sol_get_epoch_stake(core::ptr::null())
+    }
+}
+
+/// Return the active stake delegated to the given vote account in the current
+/// epoch, in lamports.
+///
+/// Returns `0` if the account is not a vote account or has no delegated stake.
+#[inline(always)]
+pub fn get_epoch_stake_for_vote_account(vote_address: &Pubkey) -> u64 {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        crate::syscalls::sol_get_epoch_stake(vote_address as *const _ as *const u8)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    unsafe {
+        crate::host::sol_get_epoch_stake(vote_address as *const _ as *const u8)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::host::{set_backend, TestBackend};
+    use std::{rc::Rc, vec};
+
+    #[test]
+    fn test_epoch_stake() {
+        assert_eq!(get_epoch_total_stake(), 0);
+
+        let backend = Rc::new(TestBackend {
+            epoch_stakes: vec![([1; 32], 100), ([2; 32], 250)],
+            ..TestBackend::default()
+        });
+        let _guard = set_backend(backend);
+
+        assert_eq!(get_epoch_total_stake(), 350);
+        assert_eq!(get_epoch_stake_for_vote_account(&[2; 32]), 250);
+        assert_eq!(get_epoch_stake_for_vote_account(&[3; 32]), 0);
+    }
+}
@@ -1,7 +1,20 @@
 //! Information about the network's clock, ticks, slots, etc.
 
-use super::Sysvar;
-use crate::impl_sysvar_get;
+use super::{is_aligned, FixedLayoutSysvar, Sysvar};
+use crate::{
+    account_info::{AccountInfo, Ref},
+    impl_sysvar_get,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The ID of the clock sysvar.
+pub const CLOCK_ID: Pubkey = [
+    6, 167, 213, 23, 24, 199, 116, 201, 40, 86, 99, 152, 105, 29, 94, 182, 139, 94, 184, 163, 155,
+    75, 109, 92, 115, 85, 91, 33, 0, 0, 0, 0,
+];
+
+declare_sysvar_id!(CLOCK_ID, Clock);
 
 /// The unit of time given to a leader for encoding a block.
 ///
@@ -62,6 +75,73 @@ pub const DEFAULT_TICKS_PER_SECOND: u64 = 160;
 // Actually calculation is supposed to be derived DEFAULT_TICKS_PER_SLOT / DEFAULT_TICKS_PER_SECOND
 pub const DEFAULT_MS_PER_SLOT: u64 = 1_000 * DEFAULT_TICKS_PER_SLOT / DEFAULT_TICKS_PER_SECOND;
 
+impl Clock {
+    /// The length of the `Clock` sysvar account data.
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8;
+
+    /// Return a `Clock` from the given account info.
+    ///
+    /// This method performs a check on the account info key.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Clock>, ProgramError> {
+        if account_info.key() != &CLOCK_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        if data.len() != Self::LEN || !is_aligned::<Self>(&data) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(data, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return a `Clock` from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        if account_info.key() != &CLOCK_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Self::from_bytes(account_info.borrow_data_unchecked())
+    }
+
+    /// Return a `Clock` from the given bytes.
+    ///
+    /// This method performs a length and alignment validation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Self::LEN || !is_aligned::<Self>(bytes) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(unsafe { Self::from_bytes_unchecked(bytes) })
+    }
+
+    /// Return a `Clock` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` has the expected length and is
+    /// suitably aligned.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes.as_ptr() as *const Clock)
+    }
+}
+
 impl Sysvar for Clock {
     impl_sysvar_get!(sol_get_clock_sysvar);
 }
+
+impl FixedLayoutSysvar for Clock {
+    const LEN: usize = Self::LEN;
+}
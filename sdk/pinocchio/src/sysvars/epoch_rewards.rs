@@ -0,0 +1,286 @@
+//! Epoch rewards for the current epoch.
+//!
+//! The sysvar is updated at the start of each epoch with the rewards to be
+//! distributed, and remains [`active`](EpochRewards::active) while the rewards
+//! are being distributed to stake accounts over several blocks.
+
+use super::{FixedLayoutSysvar, Sysvar};
+use crate::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The ID of the epoch rewards sysvar.
+pub const EPOCH_REWARDS_ID: Pubkey = [
+    6, 167, 213, 23, 24, 220, 63, 238, 2, 165, 88, 191, 131, 206, 102, 225, 68, 66, 42, 28, 52,
+    149, 11, 39, 193, 134, 155, 90, 156, 0, 0, 0,
+];
+
+declare_sysvar_id!(EPOCH_REWARDS_ID, EpochRewards);
+
+/// Epoch rewards sysvar data.
+///
+/// The type uses the same layout as the sysvar account data, where the
+/// `total_points` value is not aligned, so the values are accessed through
+/// methods.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EpochRewards {
+    /// The starting block height of the rewards distribution.
+    distribution_starting_block_height: [u8; 8],
+
+    /// Number of partitions in the rewards distribution.
+    num_partitions: [u8; 8],
+
+    /// Blockhash of the parent block of the first block in the epoch.
+    parent_blockhash: [u8; 32],
+
+    /// Total points calculated for the epoch.
+    total_points: [u8; 16],
+
+    /// Total rewards for the epoch.
+    total_rewards: [u8; 8],
+
+    /// Rewards distributed so far in the epoch.
+    distributed_rewards: [u8; 8],
+
+    /// Whether the rewards period is active.
+    active: u8,
+}
+
+impl EpochRewards {
+    /// The length of the `EpochRewards` sysvar account data.
+    pub const LEN: usize = 8 + 8 + 32 + 16 + 8 + 8 + 1;
+
+    /// Create a new `EpochRewards`.
+    pub fn new(
+        distribution_starting_block_height: u64,
+        num_partitions: u64,
+        parent_blockhash: [u8; 32],
+        total_points: u128,
+        total_rewards: u64,
+        distributed_rewards: u64,
+        active: bool,
+    ) -> Self {
+        Self {
+            distribution_starting_block_height: distribution_starting_block_height.to_le_bytes(),
+            num_partitions: num_partitions.to_le_bytes(),
+            parent_blockhash,
+            total_points: total_points.to_le_bytes(),
+            total_rewards: total_rewards.to_le_bytes(),
+            distributed_rewards: distributed_rewards.to_le_bytes(),
+            active: active as u8,
+        }
+    }
+
+    /// Return an `EpochRewards` from the given account info.
+    ///
+    /// This method performs a check on the account info key.
+    #[inline]
+    pub fn from_account_info(
+        account_info: &AccountInfo,
+    ) -> Result<Ref<EpochRewards>, ProgramError> {
+        if account_info.key() != &EPOCH_REWARDS_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(data, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return an `EpochRewards` from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        if account_info.key() != &EPOCH_REWARDS_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Self::from_bytes(account_info.borrow_data_unchecked())
+    }
+
+    /// Return an `EpochRewards` from the given bytes.
+    ///
+    /// This method performs a length validation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(unsafe { Self::from_bytes_unchecked(bytes) })
+    }
+
+    /// Return an `EpochRewards` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` has the expected length.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes.as_ptr() as *const EpochRewards)
+    }
+
+    /// The starting block height of the rewards distribution in the current
+    /// epoch.
+    #[inline(always)]
+    pub fn distribution_starting_block_height(&self) -> u64 {
+        u64::from_le_bytes(self.distribution_starting_block_height)
+    }
+
+    /// Number of partitions in the rewards distribution in the current epoch,
+    /// used to generate an `EpochRewardsHasher`.
+    #[inline(always)]
+    pub fn num_partitions(&self) -> u64 {
+        u64::from_le_bytes(self.num_partitions)
+    }
+
+    /// The blockhash of the parent block of the first block in the epoch, used
+    /// to seed an `EpochRewardsHasher`.
+    #[inline(always)]
+    pub fn parent_blockhash(&self) -> &[u8; 32] {
+        &self.parent_blockhash
+    }
+
+    /// The total rewards points calculated for the current epoch, where points
+    /// equals the sum of (delegated stake * credits observed) for all
+    /// delegations.
+    #[inline(always)]
+    pub fn total_points(&self) -> u128 {
+        u128::from_le_bytes(self.total_points)
+    }
+
+    /// The total rewards calculated for the current epoch.
+    ///
+    /// This may be greater than the total `distributed_rewards` at the end of
+    /// the rewards period, due to rounding and inability to deliver rewards
+    /// smaller than 1 lamport.
+    #[inline(always)]
+    pub fn total_rewards(&self) -> u64 {
+        u64::from_le_bytes(self.total_rewards)
+    }
+
+    /// The rewards currently distributed for the current epoch, in lamports.
+    #[inline(always)]
+    pub fn distributed_rewards(&self) -> u64 {
+        u64::from_le_bytes(self.distributed_rewards)
+    }
+
+    /// Whether the rewards period (including calculation and distribution) is
+    /// active.
+    #[inline(always)]
+    pub fn active(&self) -> bool {
+        self.active != 0
+    }
+}
+
+/// Layout of the `EpochRewards` written by the `sol_get_epoch_rewards_sysvar`
+/// syscall.
+///
+/// Unlike the account data, the syscall writes an aligned `repr(C)` struct.
+#[repr(C, align(16))]
+#[derive(Default)]
+pub(crate) struct SyscallEpochRewards {
+    distribution_starting_block_height: u64,
+    num_partitions: u64,
+    parent_blockhash: [u8; 32],
+    total_points: u128,
+    total_rewards: u64,
+    distributed_rewards: u64,
+    active: bool,
+}
+
+impl From<EpochRewards> for SyscallEpochRewards {
+    fn from(epoch_rewards: EpochRewards) -> Self {
+        Self {
+            distribution_starting_block_height: epoch_rewards.distribution_starting_block_height(),
+            num_partitions: epoch_rewards.num_partitions(),
+            parent_blockhash: epoch_rewards.parent_blockhash,
+            total_points: epoch_rewards.total_points(),
+            total_rewards: epoch_rewards.total_rewards(),
+            distributed_rewards: epoch_rewards.distributed_rewards(),
+            active: epoch_rewards.active(),
+        }
+    }
+}
+
+impl From<SyscallEpochRewards> for EpochRewards {
+    fn from(epoch_rewards: SyscallEpochRewards) -> Self {
+        Self::new(
+            epoch_rewards.distribution_starting_block_height,
+            epoch_rewards.num_partitions,
+            epoch_rewards.parent_blockhash,
+            epoch_rewards.total_points,
+            epoch_rewards.total_rewards,
+            epoch_rewards.distributed_rewards,
+            epoch_rewards.active,
+        )
+    }
+}
+
+impl Sysvar for EpochRewards {
+    fn get() -> Result<Self, ProgramError> {
+        let mut var = SyscallEpochRewards::default();
+        let var_addr = &mut var as *mut _ as *mut u8;
+
+        #[cfg(target_os = "solana")]
+        let result = unsafe { crate::syscalls::sol_get_epoch_rewards_sysvar(var_addr) };
+
+        #[cfg(not(target_os = "solana"))]
+        let result = unsafe { crate::host::sol_get_epoch_rewards_sysvar(var_addr) };
+
+        match result {
+            crate::SUCCESS => Ok(var.into()),
+            e => Err(e.into()),
+        }
+    }
+}
+
+impl FixedLayoutSysvar for EpochRewards {
+    const LEN: usize = Self::LEN;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes() {
+        let mut bytes = [0u8; EpochRewards::LEN];
+        bytes[0..8].copy_from_slice(&100u64.to_le_bytes());
+        bytes[8..16].copy_from_slice(&4u64.to_le_bytes());
+        bytes[16..48].copy_from_slice(&[7u8; 32]);
+        bytes[48..64].copy_from_slice(&(u64::MAX as u128 + 1).to_le_bytes());
+        bytes[64..72].copy_from_slice(&1_000u64.to_le_bytes());
+        bytes[72..80].copy_from_slice(&250u64.to_le_bytes());
+        bytes[80] = 1;
+
+        let epoch_rewards = EpochRewards::from_bytes(&bytes).unwrap();
+
+        assert_eq!(epoch_rewards.distribution_starting_block_height(), 100);
+        assert_eq!(epoch_rewards.num_partitions(), 4);
+        assert_eq!(epoch_rewards.parent_blockhash(), &[7u8; 32]);
+        assert_eq!(epoch_rewards.total_points(), u64::MAX as u128 + 1);
+        assert_eq!(epoch_rewards.total_rewards(), 1_000);
+        assert_eq!(epoch_rewards.distributed_rewards(), 250);
+        assert!(epoch_rewards.active());
+        assert_eq!(
+            epoch_rewards,
+            &EpochRewards::new(100, 4, [7u8; 32], u64::MAX as u128 + 1, 1_000, 250, true)
+        );
+
+        assert!(EpochRewards::from_bytes(&bytes[1..]).is_err());
+    }
+}
@@ -0,0 +1,464 @@
+//! Configuration for epochs and slots.
+//!
+//! Epochs mark a period of time composed of _slots_, for which a particular
+//! leader schedule is in effect. The epoch schedule determines the length of
+//! epochs, and the timing of the next leader-schedule selection.
+//!
+//! During the _warmup_ period, epochs start at [`MINIMUM_SLOTS_PER_EPOCH`] slots
+//! and double in length until they reach the configured slots per epoch.
+
+use super::{
+    clock::{Epoch, Slot},
+    FixedLayoutSysvar, Sysvar,
+};
+use crate::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The ID of the epoch schedule sysvar.
+pub const EPOCH_SCHEDULE_ID: Pubkey = [
+    6, 167, 213, 23, 24, 220, 63, 238, 2, 211, 228, 127, 1, 0, 248, 176, 84, 247, 148, 46, 96, 89,
+    30, 63, 80, 135, 25, 168, 5, 0, 0, 0,
+];
+
+declare_sysvar_id!(EPOCH_SCHEDULE_ID, EpochSchedule);
+
+/// The default number of slots per epoch (approximately 2 days).
+pub const DEFAULT_SLOTS_PER_EPOCH: u64 = 432_000;
+
+/// The default number of slots before an epoch starts to calculate its leader
+/// schedule.
+pub const DEFAULT_LEADER_SCHEDULE_SLOT_OFFSET: u64 = DEFAULT_SLOTS_PER_EPOCH;
+
+/// The maximum number of epochs that the leader schedule can be calculated ahead.
+pub const MAX_LEADER_SCHEDULE_EPOCH_OFFSET: u64 = 3;
+
+/// The minimum number of slots per epoch during the warmup period.
+///
+/// Based on `MAX_LOCKOUT_HISTORY` from the vote program.
+pub const MINIMUM_SLOTS_PER_EPOCH: u64 = 32;
+
+/// Number of bits to shift to compute the length of a warmup epoch.
+const MINIMUM_SLOTS_PER_EPOCH_LOG2: u32 = MINIMUM_SLOTS_PER_EPOCH.trailing_zeros();
+
+/// Epoch schedule sysvar data.
+///
+/// The type uses the same layout as the sysvar account data, where fields are not
+/// aligned, so the values are accessed through methods.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EpochSchedule {
+    /// The maximum number of slots in each epoch.
+    slots_per_epoch: [u8; 8],
+
+    /// A number of slots before beginning of an epoch to calculate a leader
+    /// schedule for that epoch.
+    leader_schedule_slot_offset: [u8; 8],
+
+    /// Whether epochs start short and grow.
+    warmup: u8,
+
+    /// The first epoch after the warmup period.
+    first_normal_epoch: [u8; 8],
+
+    /// The first slot after the warmup period.
+    first_normal_slot: [u8; 8],
+}
+
+impl EpochSchedule {
+    /// The length of the `EpochSchedule` sysvar account data.
+    pub const LEN: usize = 8 + 8 + 1 + 8 + 8;
+
+    /// Create an `EpochSchedule` without a warmup period.
+    #[inline]
+    pub fn without_warmup() -> Self {
+        Self::custom(
+            DEFAULT_SLOTS_PER_EPOCH,
+            DEFAULT_LEADER_SCHEDULE_SLOT_OFFSET,
+            false,
+        )
+    }
+
+    /// Create an `EpochSchedule` with the given configuration.
+    ///
+    /// The first normal epoch and slot are derived from `slots_per_epoch` when
+    /// `warmup` is enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slots_per_epoch` is less than [`MINIMUM_SLOTS_PER_EPOCH`].
+    pub fn custom(slots_per_epoch: u64, leader_schedule_slot_offset: u64, warmup: bool) -> Self {
+        assert!(slots_per_epoch >= MINIMUM_SLOTS_PER_EPOCH);
+
+        let (first_normal_epoch, first_normal_slot) = if warmup {
+            let next_power_of_two = slots_per_epoch.next_power_of_two();
+            let log2_slots_per_epoch = next_power_of_two
+                .trailing_zeros()
+                .saturating_sub(MINIMUM_SLOTS_PER_EPOCH_LOG2);
+
+            (
+                u64::from(log2_slots_per_epoch),
+                next_power_of_two.saturating_sub(MINIMUM_SLOTS_PER_EPOCH),
+            )
+        } else {
+            (0, 0)
+        };
+
+        Self {
+            slots_per_epoch: slots_per_epoch.to_le_bytes(),
+            leader_schedule_slot_offset: leader_schedule_slot_offset.to_le_bytes(),
+            warmup: warmup as u8,
+            first_normal_epoch: first_normal_epoch.to_le_bytes(),
+            first_normal_slot: first_normal_slot.to_le_bytes(),
+        }
+    }
+
+    /// Return an `EpochSchedule` from the given account info.
+    ///
+    /// This method performs a check on the account info key.
+    #[inline]
+    pub fn from_account_info(
+        account_info: &AccountInfo,
+    ) -> Result<Ref<EpochSchedule>, ProgramError> {
+        if account_info.key() != &EPOCH_SCHEDULE_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(data, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return an `EpochSchedule` from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        if account_info.key() != &EPOCH_SCHEDULE_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Self::from_bytes(account_info.borrow_data_unchecked())
+    }
+
+    /// Return an `EpochSchedule` from the given bytes.
+    ///
+    /// This method performs a length validation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(unsafe { Self::from_bytes_unchecked(bytes) })
+    }
+
+    /// Return an `EpochSchedule` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` has the expected length.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes.as_ptr() as *const EpochSchedule)
+    }
+
+    /// The maximum number of slots in each epoch.
+    #[inline(always)]
+    pub fn slots_per_epoch(&self) -> u64 {
+        u64::from_le_bytes(self.slots_per_epoch)
+    }
+
+    /// A number of slots before beginning of an epoch to calculate a leader
+    /// schedule for that epoch.
+    #[inline(always)]
+    pub fn leader_schedule_slot_offset(&self) -> u64 {
+        u64::from_le_bytes(self.leader_schedule_slot_offset)
+    }
+
+    /// Whether epochs start short and grow.
+    #[inline(always)]
+    pub fn warmup(&self) -> bool {
+        self.warmup != 0
+    }
+
+    /// The first epoch after the warmup period.
+    ///
+    /// This is `log2(slots_per_epoch) - log2(MINIMUM_SLOTS_PER_EPOCH)`.
+    #[inline(always)]
+    pub fn first_normal_epoch(&self) -> Epoch {
+        u64::from_le_bytes(self.first_normal_epoch)
+    }
+
+    /// The first slot after the warmup period.
+    ///
+    /// This is `MINIMUM_SLOTS_PER_EPOCH * (2.pow(first_normal_epoch) - 1)`.
+    #[inline(always)]
+    pub fn first_normal_slot(&self) -> Slot {
+        u64::from_le_bytes(self.first_normal_slot)
+    }
+
+    /// Return the number of slots in the given epoch.
+    pub fn get_slots_in_epoch(&self, epoch: Epoch) -> u64 {
+        if epoch < self.first_normal_epoch() {
+            2u64.saturating_pow((epoch as u32).saturating_add(MINIMUM_SLOTS_PER_EPOCH_LOG2))
+        } else {
+            self.slots_per_epoch()
+        }
+    }
+
+    /// Return the epoch for which the leader schedule should be generated when
+    /// at the given slot.
+    pub fn get_leader_schedule_epoch(&self, slot: Slot) -> Epoch {
+        let first_normal_slot = self.first_normal_slot();
+
+        if slot < first_normal_slot {
+            // until we get to normal slots, behave as if the leader schedule slot
+            // offset is equal to the slots in the epoch
+            self.get_epoch_and_slot_index(slot).0.saturating_add(1)
+        } else {
+            let new_slots_since_first_normal_slot = slot.saturating_sub(first_normal_slot);
+            let new_first_normal_leader_schedule_slot = new_slots_since_first_normal_slot
+                .saturating_add(self.leader_schedule_slot_offset());
+            let new_epochs_since_first_normal_leader_schedule =
+                new_first_normal_leader_schedule_slot
+                    .checked_div(self.slots_per_epoch())
+                    .unwrap_or(0);
+
+            self.first_normal_epoch()
+                .saturating_add(new_epochs_since_first_normal_leader_schedule)
+        }
+    }
+
+    /// Return the epoch that contains the given slot.
+    #[inline]
+    pub fn get_epoch(&self, slot: Slot) -> Epoch {
+        self.get_epoch_and_slot_index(slot).0
+    }
+
+    /// Return the epoch that contains the given slot and the index of the slot
+    /// within that epoch.
+    pub fn get_epoch_and_slot_index(&self, slot: Slot) -> (Epoch, u64) {
+        let first_normal_slot = self.first_normal_slot();
+
+        if slot < first_normal_slot {
+            let epoch = slot
+                .saturating_add(MINIMUM_SLOTS_PER_EPOCH)
+                .saturating_add(1)
+                .next_power_of_two()
+                .trailing_zeros()
+                .saturating_sub(MINIMUM_SLOTS_PER_EPOCH_LOG2)
+                .saturating_sub(1);
+
+            let epoch_len = 2u64.saturating_pow(epoch.saturating_add(MINIMUM_SLOTS_PER_EPOCH_LOG2));
+
+            (
+                u64::from(epoch),
+                slot.saturating_sub(epoch_len.saturating_sub(MINIMUM_SLOTS_PER_EPOCH)),
+            )
+        } else {
+            let slots_per_epoch = self.slots_per_epoch();
+            let normal_slot_index = slot.saturating_sub(first_normal_slot);
+            let normal_epoch_index = normal_slot_index.checked_div(slots_per_epoch).unwrap_or(0);
+
+            (
+                self.first_normal_epoch().saturating_add(normal_epoch_index),
+                normal_slot_index.checked_rem(slots_per_epoch).unwrap_or(0),
+            )
+        }
+    }
+
+    /// Return the first slot of the given epoch.
+    pub fn get_first_slot_in_epoch(&self, epoch: Epoch) -> Slot {
+        let first_normal_epoch = self.first_normal_epoch();
+
+        if epoch <= first_normal_epoch {
+            2u64.saturating_pow(epoch as u32)
+                .saturating_sub(1)
+                .saturating_mul(MINIMUM_SLOTS_PER_EPOCH)
+        } else {
+            epoch
+                .saturating_sub(first_normal_epoch)
+                .saturating_mul(self.slots_per_epoch())
+                .saturating_add(self.first_normal_slot())
+        }
+    }
+
+    /// Return the last slot of the given epoch.
+    #[inline]
+    pub fn get_last_slot_in_epoch(&self, epoch: Epoch) -> Slot {
+        self.get_first_slot_in_epoch(epoch)
+            .saturating_add(self.get_slots_in_epoch(epoch))
+            .saturating_sub(1)
+    }
+}
+
+impl Default for EpochSchedule {
+    fn default() -> Self {
+        Self::custom(
+            DEFAULT_SLOTS_PER_EPOCH,
+            DEFAULT_LEADER_SCHEDULE_SLOT_OFFSET,
+            true,
+        )
+    }
+}
+
+/// Layout of the `EpochSchedule` written by the `sol_get_epoch_schedule_sysvar`
+/// syscall.
+///
+/// Unlike the account data, the syscall writes an aligned `repr(C)` struct.
+#[repr(C)]
+#[derive(Default)]
+pub(crate) struct SyscallEpochSchedule {
+    slots_per_epoch: u64,
+    leader_schedule_slot_offset: u64,
+    warmup: bool,
+    first_normal_epoch: u64,
+    first_normal_slot: u64,
+}
+
+impl From<EpochSchedule> for SyscallEpochSchedule {
+    fn from(epoch_schedule: EpochSchedule) -> Self {
+        Self {
+            slots_per_epoch: epoch_schedule.slots_per_epoch(),
+            leader_schedule_slot_offset: epoch_schedule.leader_schedule_slot_offset(),
+            warmup: epoch_schedule.warmup(),
+            first_normal_epoch: epoch_schedule.first_normal_epoch(),
+            first_normal_slot: epoch_schedule.first_normal_slot(),
+        }
+    }
+}
+
+impl From<SyscallEpochSchedule> for EpochSchedule {
+    fn from(epoch_schedule: SyscallEpochSchedule) -> Self {
+        Self {
+            slots_per_epoch: epoch_schedule.slots_per_epoch.to_le_bytes(),
+            leader_schedule_slot_offset: epoch_schedule.leader_schedule_slot_offset.to_le_bytes(),
+            warmup: epoch_schedule.warmup as u8,
+            first_normal_epoch: epoch_schedule.first_normal_epoch.to_le_bytes(),
+            first_normal_slot: epoch_schedule.first_normal_slot.to_le_bytes(),
+        }
+    }
+}
+
+impl Sysvar for EpochSchedule {
+    fn get() -> Result<Self, ProgramError> {
+        let mut var = SyscallEpochSchedule::default();
+        let var_addr = &mut var as *mut _ as *mut u8;
+
+        #[cfg(target_os = "solana")]
+        let result = unsafe { crate::syscalls::sol_get_epoch_schedule_sysvar(var_addr) };
+
+        #[cfg(not(target_os = "solana"))]
+        let result = unsafe { crate::host::sol_get_epoch_schedule_sysvar(var_addr) };
+
+        match result {
+            crate::SUCCESS => Ok(var.into()),
+            e => Err(e.into()),
+        }
+    }
+}
+
+impl FixedLayoutSysvar for EpochSchedule {
+    const LEN: usize = Self::LEN;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_schedule() {
+        for slots_per_epoch in MINIMUM_SLOTS_PER_EPOCH..=MINIMUM_SLOTS_PER_EPOCH * 16 {
+            let epoch_schedule = EpochSchedule::custom(slots_per_epoch, slots_per_epoch / 2, true);
+
+            assert_eq!(epoch_schedule.get_first_slot_in_epoch(0), 0);
+            assert_eq!(
+                epoch_schedule.get_last_slot_in_epoch(0),
+                MINIMUM_SLOTS_PER_EPOCH - 1
+            );
+
+            let mut last_leader_schedule = 0;
+            let mut last_epoch = 0;
+            let mut last_slots_in_epoch = MINIMUM_SLOTS_PER_EPOCH;
+
+            for slot in 0..(2 * slots_per_epoch) {
+                // verify that the leader schedule epoch increases monotonically
+                let leader_schedule = epoch_schedule.get_leader_schedule_epoch(slot);
+                if leader_schedule != last_leader_schedule {
+                    assert_eq!(leader_schedule, last_leader_schedule + 1);
+                    last_leader_schedule = leader_schedule;
+                }
+
+                let (epoch, offset) = epoch_schedule.get_epoch_and_slot_index(slot);
+
+                // verify that the epoch and slot index are consistent
+                if epoch != last_epoch {
+                    assert_eq!(epoch, last_epoch + 1);
+                    last_epoch = epoch;
+                    assert_eq!(epoch_schedule.get_first_slot_in_epoch(epoch), slot);
+                    assert_eq!(epoch_schedule.get_last_slot_in_epoch(epoch - 1), slot - 1);
+
+                    // epochs double in length during warmup
+                    let slots_in_epoch = epoch_schedule.get_slots_in_epoch(epoch);
+                    if slots_in_epoch != last_slots_in_epoch && slots_in_epoch != slots_per_epoch {
+                        assert_eq!(slots_in_epoch, last_slots_in_epoch * 2);
+                    }
+                    last_slots_in_epoch = slots_in_epoch;
+                }
+
+                assert_eq!(epoch_schedule.get_epoch(slot), epoch);
+                assert!(offset < last_slots_in_epoch);
+            }
+
+            // the last slot computed should be in the last epoch
+            assert!(last_leader_schedule != 0);
+            assert!(last_epoch != 0);
+            assert!(last_slots_in_epoch == slots_per_epoch);
+        }
+    }
+
+    #[test]
+    fn test_without_warmup() {
+        let epoch_schedule = EpochSchedule::without_warmup();
+
+        assert!(!epoch_schedule.warmup());
+        assert_eq!(epoch_schedule.first_normal_epoch(), 0);
+        assert_eq!(epoch_schedule.first_normal_slot(), 0);
+        assert_eq!(
+            epoch_schedule.get_epoch_and_slot_index(DEFAULT_SLOTS_PER_EPOCH + 5),
+            (1, 5)
+        );
+        assert_eq!(
+            epoch_schedule.get_last_slot_in_epoch(1),
+            2 * DEFAULT_SLOTS_PER_EPOCH - 1
+        );
+        assert_eq!(epoch_schedule.get_leader_schedule_epoch(0), 1);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let mut bytes = [0u8; EpochSchedule::LEN];
+        bytes[0..8].copy_from_slice(&8192u64.to_le_bytes());
+        bytes[8..16].copy_from_slice(&8192u64.to_le_bytes());
+        bytes[16] = 1;
+        bytes[17..25].copy_from_slice(&8u64.to_le_bytes());
+        bytes[25..33].copy_from_slice(&8160u64.to_le_bytes());
+
+        let epoch_schedule = EpochSchedule::from_bytes(&bytes).unwrap();
+        assert_eq!(epoch_schedule, &EpochSchedule::custom(8192, 8192, true));
+
+        assert!(EpochSchedule::from_bytes(&bytes[1..]).is_err());
+    }
+}
@@ -1,9 +1,25 @@
 //! Calculation of transaction fees.
 
-use super::{clock::DEFAULT_MS_PER_SLOT, Sysvar};
-use crate::impl_sysvar_get;
+use super::{clock::DEFAULT_MS_PER_SLOT, is_aligned, Sysvar};
+use crate::{
+    account_info::{AccountInfo, Ref},
+    impl_sysvar_get,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The ID of the fees sysvar.
+pub const FEES_ID: Pubkey = [
+    6, 167, 213, 23, 24, 226, 90, 141, 131, 80, 60, 37, 26, 122, 240, 113, 38, 253, 114, 0, 223,
+    111, 196, 237, 82, 106, 156, 144, 0, 0, 0, 0,
+];
+
+declare_sysvar_id!(FEES_ID, FeeCalculator);
 
 /// Fee calculator for processing transactions
+///
+/// This is the data held by the fees sysvar account.
+#[repr(C)]
 #[derive(Debug, Default, Clone, Copy)]
 pub struct FeeCalculator {
     /// The current cost of a signature in lamports.
@@ -13,12 +29,73 @@ pub struct FeeCalculator {
 }
 
 impl FeeCalculator {
+    /// The length of the `FeeCalculator` in the fees sysvar account data.
+    pub const LEN: usize = 8;
+
     /// Create a new instance of the FeeCalculator
     pub fn new(lamports_per_signature: u64) -> Self {
         Self {
             lamports_per_signature,
         }
     }
+
+    /// Return the `FeeCalculator` of the fees sysvar from the given account info.
+    ///
+    /// This method performs a check on the account info key.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
+        if account_info.key() != &FEES_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        if data.len() != Self::LEN || !is_aligned::<Self>(&data) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(data, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return the `FeeCalculator` of the fees sysvar from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        if account_info.key() != &FEES_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Self::from_bytes(account_info.borrow_data_unchecked())
+    }
+
+    /// Return a `FeeCalculator` from the given bytes.
+    ///
+    /// This method performs a length and alignment validation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Self::LEN || !is_aligned::<Self>(bytes) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(unsafe { Self::from_bytes_unchecked(bytes) })
+    }
+
+    /// Return a `FeeCalculator` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` has the expected length and is
+    /// suitably aligned.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes.as_ptr() as *const FeeCalculator)
+    }
 }
 
 /// Governs the fee rate for the cluster
@@ -83,6 +160,11 @@ pub struct Fees {
 }
 
 impl Fees {
+    /// The length of the `Fees` sysvar account data.
+    ///
+    /// The account data only holds the [`FeeCalculator`].
+    pub const LEN: usize = FeeCalculator::LEN;
+
     /// Create a new instance of the Fees sysvar
     pub fn new(fee_calculator: FeeCalculator, fee_rate_governor: FeeRateGovernor) -> Self {
         Self {
@@ -90,6 +172,33 @@ impl Fees {
             fee_rate_governor,
         }
     }
+
+    /// Return the `FeeCalculator` of the `Fees` sysvar from the given account info.
+    ///
+    /// Since the account data only holds the fee calculator, this method does not
+    /// return a `Fees`. This method performs a check on the account info key.
+    #[inline(always)]
+    pub fn from_account_info(
+        account_info: &AccountInfo,
+    ) -> Result<Ref<FeeCalculator>, ProgramError> {
+        FeeCalculator::from_account_info(account_info)
+    }
+
+    /// Return the `FeeCalculator` of the `Fees` sysvar from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline(always)]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&FeeCalculator, ProgramError> {
+        FeeCalculator::from_account_info_unchecked(account_info)
+    }
 }
 
 impl Sysvar for Fees {
@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::instruction::Instruction;
 use crate::{
     account_info::{AccountInfo, Ref},
     instruction::AccountMeta,
@@ -6,6 +8,8 @@ use crate::{
 };
 
 use core::{marker::PhantomData, mem::size_of, ops::Deref};
+#[cfg(feature = "std")]
+use std::vec::Vec;
 
 /// Sysvar1nstructions1111111111111111111111111
 pub const INSTRUCTIONS_ID: Pubkey = [
@@ -13,6 +17,16 @@ pub const INSTRUCTIONS_ID: Pubkey = [
     0xc1, 0x24, 0xc6, 0x8f, 0x21, 0x56, 0x75, 0xa5, 0xdb, 0xba, 0xcb, 0x5f, 0x08, 0x00, 0x00, 0x00,
 ];
 
+declare_sysvar_id!(INSTRUCTIONS_ID);
+
+/// Length of the `u16` values of the sysvar account data.
+const U16_LEN: usize = size_of::<u16>();
+
+/// Instructions sysvar data.
+///
+/// The account data holds the number of instructions of the transaction, the
+/// offset of each instruction, the serialized instructions and, in its last 2
+/// bytes, the index of the currently executing instruction.
 pub struct Instructions<T>
 where
     T: Deref<Target = [u8]>,
@@ -24,26 +38,50 @@ impl<T> Instructions<T>
 where
     T: Deref<Target = [u8]>,
 {
+    /// Creates a new `Instructions` struct.
+    ///
+    /// `data` is the instructions sysvar account data. This method validates the
+    /// serialized layout of all instructions, so the instructions can be accessed
+    /// without further bounds checks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidAccountData`] if `data` is not a valid
+    /// instructions sysvar account data.
+    #[inline]
+    pub fn new(data: T) -> Result<Self, ProgramError> {
+        validate(&data)?;
+        Ok(Instructions { data })
+    }
+
     /// Creates a new `Instructions` struct.
     ///
     /// `data` is the instructions sysvar account data.
     ///
     /// # Safety
     ///
-    /// This function is unsafe because it does not check if the provided data is from the Sysvar Account.
+    /// This function is unsafe because it does not check if the provided data is from the Sysvar Account,
+    /// nor validates its layout.
     #[inline(always)]
     pub unsafe fn new_unchecked(data: T) -> Self {
         Instructions { data }
     }
 
+    /// Return the number of instructions in the currently executing `Transaction`.
+    #[inline(always)]
+    pub fn num_instructions(&self) -> usize {
+        // SAFETY: The first 2 bytes of the Instructions sysvar data represents the
+        // number of instructions.
+        unsafe { read_u16_unchecked(&self.data, 0) as usize }
+    }
+
     /// Load the current `Instruction`'s index in the currently executing
     /// `Transaction`.
     #[inline(always)]
     pub fn load_current_index(&self) -> u16 {
-        let len = self.data.len();
         // SAFETY: The last 2 bytes of the Instructions sysvar data represents the current
         // instruction index.
-        unsafe { u16::from_le_bytes(*(self.data.as_ptr().add(len - 2) as *const [u8; 2])) }
+        unsafe { read_u16_unchecked(&self.data, self.data.len() - U16_LEN) }
     }
 
     /// Creates and returns an `IntrospectedInstruction` for the instruction at the specified index.
@@ -58,15 +96,7 @@ where
         &self,
         index: usize,
     ) -> IntrospectedInstruction {
-        let offset = *(self
-            .data
-            .as_ptr()
-            .add(size_of::<u16>() + index * size_of::<u16>()) as *const u16);
-
-        IntrospectedInstruction {
-            raw: self.data.as_ptr().add(offset as usize),
-            marker: PhantomData,
-        }
+        instruction_at(&self.data, index)
     }
 
     /// Creates and returns an `IntrospectedInstruction` for the instruction at the specified index.
@@ -75,11 +105,7 @@ where
         &self,
         index: usize,
     ) -> Result<IntrospectedInstruction, ProgramError> {
-        // SAFETY: The first 2 bytes of the Instructions sysvar data represents the
-        // number of instructions.
-        let num_instructions = unsafe { *(self.data.as_ptr() as *const u16) };
-
-        if index >= num_instructions as usize {
+        if index >= self.num_instructions() {
             return Err(ProgramError::InvalidInstructionData);
         }
 
@@ -103,6 +129,75 @@ where
 
         self.load_instruction_at(index as usize)
     }
+
+    /// Return an iterator over all instructions of the currently executing `Transaction`.
+    #[inline]
+    pub fn iter(&self) -> IntrospectedInstructions {
+        IntrospectedInstructions {
+            data: &self.data,
+            index: 0,
+            end: self.num_instructions(),
+        }
+    }
+
+    /// Return an iterator over the instructions that precede the current `Instruction`.
+    #[inline]
+    pub fn instructions_before_current(&self) -> IntrospectedInstructions {
+        let current_index = self.current_index();
+        IntrospectedInstructions {
+            data: &self.data,
+            index: 0,
+            end: current_index,
+        }
+    }
+
+    /// Return an iterator over the instructions that follow the current `Instruction`.
+    #[inline]
+    pub fn instructions_after_current(&self) -> IntrospectedInstructions {
+        let num_instructions = self.num_instructions();
+        IntrospectedInstructions {
+            data: &self.data,
+            index: (self.current_index() + 1).min(num_instructions),
+            end: num_instructions,
+        }
+    }
+
+    /// Return the first instruction of the currently executing `Transaction` that
+    /// invokes the given program, if any.
+    #[inline]
+    pub fn find_by_program_id(&self, program_id: &Pubkey) -> Option<IntrospectedInstruction> {
+        self.iter()
+            .find(|instruction| instruction.get_program_id() == program_id)
+    }
+
+    /// Return the number of instructions of the currently executing `Transaction`
+    /// that invoke the given program.
+    #[inline]
+    pub fn count_invocations_of(&self, program_id: &Pubkey) -> usize {
+        self.iter()
+            .filter(|instruction| instruction.get_program_id() == program_id)
+            .count()
+    }
+
+    /// Return the current `Instruction`'s index, bounded by the number of instructions.
+    #[inline(always)]
+    fn current_index(&self) -> usize {
+        (self.load_current_index() as usize).min(self.num_instructions())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Instructions<T>
+where
+    T: Deref<Target = [u8]>,
+{
+    type Item = IntrospectedInstruction<'a>;
+
+    type IntoIter = IntrospectedInstructions<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl<'a> TryFrom<&'a AccountInfo> for Instructions<Ref<'a, [u8]>> {
@@ -114,12 +209,128 @@ impl<'a> TryFrom<&'a AccountInfo> for Instructions<Ref<'a, [u8]>> {
             return Err(ProgramError::UnsupportedSysvar);
         }
 
-        Ok(Instructions {
-            data: account_info.try_borrow_data()?,
-        })
+        Instructions::new(account_info.try_borrow_data()?)
+    }
+}
+
+/// Iterator over the instructions of the instructions sysvar.
+#[derive(Clone)]
+pub struct IntrospectedInstructions<'a> {
+    /// The validated sysvar account data.
+    data: &'a [u8],
+
+    /// Index of the next instruction.
+    index: usize,
+
+    /// Index after the last instruction.
+    end: usize,
+}
+
+impl<'a> Iterator for IntrospectedInstructions<'a> {
+    type Item = IntrospectedInstruction<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.end {
+            // SAFETY: The index is within the number of instructions of the
+            // validated data.
+            let instruction = unsafe { instruction_at(self.data, self.index) };
+            self.index += 1;
+            Some(instruction)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for IntrospectedInstructions<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index < self.end {
+            self.end -= 1;
+            // SAFETY: The index is within the number of instructions of the
+            // validated data.
+            Some(unsafe { instruction_at(self.data, self.end) })
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for IntrospectedInstructions<'_> {}
+
+/// Read a little-endian `u16` at `offset`, if within `data`.
+#[inline(always)]
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset.checked_add(U16_LEN)?)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// Read a little-endian `u16` at `offset`.
+///
+/// # Safety
+///
+/// The caller must ensure that `offset + 2` is within `data`.
+#[inline(always)]
+unsafe fn read_u16_unchecked(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes(*(data.as_ptr().add(offset) as *const [u8; 2]))
+}
+
+/// Return the instruction at `index`.
+///
+/// # Safety
+///
+/// The caller must ensure that `data` was validated and `index` is within the
+/// number of instructions.
+#[inline(always)]
+unsafe fn instruction_at(data: &[u8], index: usize) -> IntrospectedInstruction {
+    let offset = read_u16_unchecked(data, U16_LEN + index * U16_LEN);
+
+    IntrospectedInstruction {
+        raw: data.as_ptr().add(offset as usize),
+        marker: PhantomData,
     }
 }
 
+/// Validate the layout of the instructions sysvar account data.
+///
+/// This checks that the offsets of all instructions are within the data and that
+/// each instruction, including its account metas and data, ends before the
+/// current instruction index.
+fn validate(data: &[u8]) -> Result<(), ProgramError> {
+    // the current instruction index is stored in the last 2 bytes
+    let end = data
+        .len()
+        .checked_sub(U16_LEN)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let data = &data[..end];
+
+    let num_instructions = read_u16(data, 0).ok_or(ProgramError::InvalidAccountData)? as usize;
+
+    for index in 0..num_instructions {
+        let offset = read_u16(data, U16_LEN + index * U16_LEN)
+            .ok_or(ProgramError::InvalidAccountData)? as usize;
+        let num_accounts = read_u16(data, offset).ok_or(ProgramError::InvalidAccountData)? as usize;
+
+        let data_len_offset =
+            offset + U16_LEN + num_accounts * IntrospectedAccountMeta::LEN + PUBKEY_BYTES;
+        let data_len =
+            read_u16(data, data_len_offset).ok_or(ProgramError::InvalidAccountData)? as usize;
+
+        if data_len_offset + U16_LEN + data_len > data.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+
+    Ok(())
+}
+
 #[repr(C)]
 #[derive(Clone, PartialEq, Eq)]
 pub struct IntrospectedInstruction<'a> {
@@ -127,7 +338,7 @@ pub struct IntrospectedInstruction<'a> {
     pub marker: PhantomData<&'a [u8]>,
 }
 
-impl IntrospectedInstruction<'_> {
+impl<'a> IntrospectedInstruction<'a> {
     /// Get the account meta at the specified index.
     ///
     /// # Safety
@@ -138,7 +349,10 @@ impl IntrospectedInstruction<'_> {
     /// performs the necessary index verification. However, to optimize performance for users
     /// who are sure that the index is in bounds, we have exposed it as an unsafe function.
     #[inline(always)]
-    pub unsafe fn get_account_meta_at_unchecked(&self, index: usize) -> &IntrospectedAccountMeta {
+    pub unsafe fn get_account_meta_at_unchecked(
+        &self,
+        index: usize,
+    ) -> &'a IntrospectedAccountMeta {
         let offset = core::mem::size_of::<u16>() + (index * IntrospectedAccountMeta::LEN);
         &*(self.raw.add(offset) as *const IntrospectedAccountMeta)
     }
@@ -152,7 +366,7 @@ impl IntrospectedInstruction<'_> {
     pub fn get_account_meta_at(
         &self,
         index: usize,
-    ) -> Result<&IntrospectedAccountMeta, ProgramError> {
+    ) -> Result<&'a IntrospectedAccountMeta, ProgramError> {
         // SAFETY: The first 2 bytes represent the number of accounts in the instruction.
         let num_accounts = u16::from_le_bytes(unsafe { *(self.raw as *const [u8; 2]) });
 
@@ -166,7 +380,7 @@ impl IntrospectedInstruction<'_> {
 
     /// Get the program ID of the `Instruction`.
     #[inline(always)]
-    pub fn get_program_id(&self) -> &Pubkey {
+    pub fn get_program_id(&self) -> &'a Pubkey {
         // SAFETY: The first 2 bytes represent the number of accounts in the instruction.
         let num_accounts = u16::from_le_bytes(unsafe { *(self.raw as *const [u8; 2]) });
 
@@ -180,7 +394,7 @@ impl IntrospectedInstruction<'_> {
 
     /// Get the instruction data of the `Instruction`.
     #[inline(always)]
-    pub fn get_instruction_data(&self) -> &[u8] {
+    pub fn get_instruction_data(&self) -> &'a [u8] {
         // SAFETY: The first 2 bytes represent the number of accounts in the instruction.
         let offset = u16::from_le_bytes(unsafe { *(self.raw as *const [u8; 2]) }) as usize
             * size_of::<IntrospectedAccountMeta>()
@@ -240,3 +454,318 @@ impl IntrospectedAccountMeta {
         AccountMeta::new(&self.key, self.is_writable(), self.is_signer())
     }
 }
+
+/// Builder for the instructions sysvar account data.
+///
+/// The resulting bytes have the same layout as the account data the runtime
+/// provides for the instructions sysvar, so introspection logic can be exercised
+/// in native tests, either with [`Instructions::new`] or as the data of an
+/// [`InputAccount`](crate::entrypoint::InputAccount).
+///
+/// # Example
+///
+/// ```
+/// use pinocchio::{
+///     instruction::{AccountMeta, Instruction},
+///     sysvars::instructions::{Instructions, InstructionsBuilder},
+/// };
+///
+/// let program_id = [1u8; 32];
+/// let data = InstructionsBuilder::new()
+///     .instruction(&Instruction {
+///         program_id: &program_id,
+///         data: &[1, 2, 3],
+///         accounts: &[AccountMeta::writable_signer(&[2u8; 32])],
+///     })
+///     .current_index(0)
+///     .build();
+///
+/// let instructions = Instructions::new(data.as_slice()).unwrap();
+/// assert_eq!(instructions.count_invocations_of(&program_id), 1);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct InstructionsBuilder {
+    /// The serialized instructions.
+    instructions: Vec<Vec<u8>>,
+
+    /// Index of the currently executing instruction.
+    current_index: u16,
+}
+
+#[cfg(feature = "std")]
+impl InstructionsBuilder {
+    /// Creates a new `InstructionsBuilder` with no instructions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an instruction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of accounts or the length of the data of the
+    /// instruction does not fit in a `u16`.
+    pub fn instruction(mut self, instruction: &Instruction) -> Self {
+        let mut bytes = Vec::with_capacity(
+            U16_LEN
+                + instruction.accounts.len() * IntrospectedAccountMeta::LEN
+                + PUBKEY_BYTES
+                + U16_LEN
+                + instruction.data.len(),
+        );
+
+        let accounts_len =
+            u16::try_from(instruction.accounts.len()).expect("number of accounts exceeds u16::MAX");
+        bytes.extend_from_slice(&accounts_len.to_le_bytes());
+        for account in instruction.accounts {
+            let mut flags = 0;
+            if account.is_signer {
+                flags |= IS_SIGNER;
+            }
+            if account.is_writable {
+                flags |= IS_WRITABLE;
+            }
+            bytes.push(flags);
+            bytes.extend_from_slice(account.pubkey);
+        }
+        bytes.extend_from_slice(instruction.program_id);
+        let data_len = u16::try_from(instruction.data.len())
+            .expect("instruction data length exceeds u16::MAX");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        bytes.extend_from_slice(instruction.data);
+
+        self.instructions.push(bytes);
+        self
+    }
+
+    /// Sets the index of the currently executing instruction.
+    pub fn current_index(mut self, index: u16) -> Self {
+        self.current_index = index;
+        self
+    }
+
+    /// Serializes the instructions sysvar account data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of instructions or an instruction offset does not fit
+    /// in a `u16`.
+    pub fn build(self) -> Vec<u8> {
+        let header_len = U16_LEN + self.instructions.len() * U16_LEN;
+        let mut bytes = Vec::with_capacity(
+            header_len + self.instructions.iter().map(Vec::len).sum::<usize>() + U16_LEN,
+        );
+
+        let instructions_len = u16::try_from(self.instructions.len())
+            .expect("number of instructions exceeds u16::MAX");
+        bytes.extend_from_slice(&instructions_len.to_le_bytes());
+
+        let mut offset = header_len;
+        for instruction in &self.instructions {
+            let offset_u16 = u16::try_from(offset).expect("instruction offset exceeds u16::MAX");
+            bytes.extend_from_slice(&offset_u16.to_le_bytes());
+            offset += instruction.len();
+        }
+
+        for instruction in &self.instructions {
+            bytes.extend_from_slice(instruction);
+        }
+
+        bytes.extend_from_slice(&self.current_index.to_le_bytes());
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    /// An instruction as `(program_id, accounts, data)`, where accounts are
+    /// `(key, flags)` pairs.
+    type TestInstruction<'a> = (Pubkey, &'a [(Pubkey, u8)], &'a [u8]);
+
+    /// Serialize the instructions sysvar account data with the given instructions.
+    fn account_data(instructions: &[TestInstruction], current: u16) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(instructions.len() as u16).to_le_bytes());
+        data.resize(U16_LEN + instructions.len() * U16_LEN, 0);
+
+        for (index, (program_id, accounts, instruction_data)) in instructions.iter().enumerate() {
+            let offset = (data.len() as u16).to_le_bytes();
+            data[U16_LEN + index * U16_LEN..][..U16_LEN].copy_from_slice(&offset);
+
+            data.extend_from_slice(&(accounts.len() as u16).to_le_bytes());
+            for (key, flags) in accounts.iter() {
+                data.push(*flags);
+                data.extend_from_slice(key);
+            }
+            data.extend_from_slice(program_id);
+            data.extend_from_slice(&(instruction_data.len() as u16).to_le_bytes());
+            data.extend_from_slice(instruction_data);
+        }
+
+        data.extend_from_slice(&current.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_iter() {
+        let data = account_data(
+            &[
+                ([1; 32], &[([5; 32], IS_SIGNER | IS_WRITABLE)], &[1, 2, 3]),
+                ([2; 32], &[], &[]),
+                ([1; 32], &[([6; 32], 0), ([7; 32], IS_WRITABLE)], &[4]),
+            ],
+            1,
+        );
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+
+        assert_eq!(instructions.num_instructions(), 3);
+        assert_eq!(instructions.load_current_index(), 1);
+        assert_eq!(instructions.iter().len(), 3);
+
+        let program_ids: Vec<Pubkey> = instructions
+            .iter()
+            .map(|instruction| *instruction.get_program_id())
+            .collect();
+        assert_eq!(program_ids, [[1; 32], [2; 32], [1; 32]]);
+
+        let last = instructions.iter().next_back().unwrap();
+        assert_eq!(last.get_instruction_data(), &[4]);
+        assert!(last.get_account_meta_at(1).unwrap().is_writable());
+        assert!(last.get_account_meta_at(2).is_err());
+
+        let first = instructions.find_by_program_id(&[1; 32]).unwrap();
+        assert_eq!(first.get_instruction_data(), &[1, 2, 3]);
+        assert!(first.get_account_meta_at(0).unwrap().is_signer());
+        assert!(instructions.find_by_program_id(&[3; 32]).is_none());
+
+        assert_eq!(instructions.count_invocations_of(&[1; 32]), 2);
+        assert_eq!(instructions.count_invocations_of(&[3; 32]), 0);
+
+        assert_eq!(instructions.instructions_before_current().len(), 1);
+        let mut after = instructions.instructions_after_current();
+        assert_eq!(after.len(), 1);
+        assert_eq!(after.next().unwrap().get_instruction_data(), &[4]);
+
+        assert!(instructions.load_instruction_at(3).is_err());
+        assert_eq!(
+            instructions
+                .get_instruction_relative(-1)
+                .unwrap()
+                .get_program_id(),
+            &[1; 32]
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        let data = account_data(&[([1; 32], &[([5; 32], 0)], &[1, 2, 3])], 0);
+        assert!(Instructions::new(data.as_slice()).is_ok());
+
+        // truncated instruction data
+        let mut truncated = data[..data.len() - 3].to_vec();
+        truncated.extend_from_slice(&0u16.to_le_bytes());
+        assert_eq!(
+            Instructions::new(truncated.as_slice()).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+
+        // instruction offset out of bounds
+        let mut invalid_offset = data.clone();
+        invalid_offset[2..4].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(Instructions::new(invalid_offset.as_slice()).is_err());
+
+        // number of instructions larger than the offsets
+        let mut invalid_count = data.clone();
+        invalid_count[..2].copy_from_slice(&100u16.to_le_bytes());
+        assert!(Instructions::new(invalid_count.as_slice()).is_err());
+
+        assert!(Instructions::new(&[][..]).is_err());
+        assert!(Instructions::new(&[0, 0][..]).is_err());
+
+        let empty = account_data(&[], 0);
+        let instructions = Instructions::new(empty.as_slice()).unwrap();
+        assert_eq!(instructions.iter().count(), 0);
+        assert_eq!(instructions.instructions_after_current().count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_builder() {
+        use crate::{
+            entrypoint::{deserialize, InputAccount, InputBuilder},
+            instruction::Instruction,
+        };
+        use core::mem::MaybeUninit;
+
+        let data = InstructionsBuilder::new()
+            .instruction(&Instruction {
+                program_id: &[1; 32],
+                data: &[1, 2, 3],
+                accounts: &[AccountMeta::writable_signer(&[5; 32])],
+            })
+            .instruction(&Instruction {
+                program_id: &[2; 32],
+                data: &[],
+                accounts: &[],
+            })
+            .instruction(&Instruction {
+                program_id: &[1; 32],
+                data: &[4],
+                accounts: &[
+                    AccountMeta::readonly(&[6; 32]),
+                    AccountMeta::writable(&[7; 32]),
+                ],
+            })
+            .current_index(1)
+            .build();
+
+        assert_eq!(
+            data,
+            account_data(
+                &[
+                    ([1; 32], &[([5; 32], IS_SIGNER | IS_WRITABLE)], &[1, 2, 3]),
+                    ([2; 32], &[], &[]),
+                    ([1; 32], &[([6; 32], 0), ([7; 32], IS_WRITABLE)], &[4]),
+                ],
+                1,
+            )
+        );
+        assert_eq!(InstructionsBuilder::new().build(), account_data(&[], 0));
+
+        let mut input = InputBuilder::new(&[1; 32])
+            .account(InputAccount::new(INSTRUCTIONS_ID, [0; 32]).data(&data))
+            .build();
+
+        const UNINIT: MaybeUninit<AccountInfo> = MaybeUninit::<AccountInfo>::uninit();
+        let mut accounts = [UNINIT; 1];
+        unsafe { deserialize::<1>(input.as_mut_ptr(), &mut accounts) };
+
+        let instructions =
+            Instructions::try_from(unsafe { accounts[0].assume_init_ref() }).unwrap();
+        assert_eq!(instructions.load_current_index(), 1);
+
+        let instruction = instructions.get_instruction_relative(1).unwrap();
+        let meta = instruction.get_account_meta_at(1).unwrap();
+        assert!(meta.is_writable() && !meta.is_signer());
+        assert_eq!(meta.key, [7; 32]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(expected = "instruction data length exceeds u16::MAX")]
+    fn test_builder_data_overflow() {
+        let data = std::vec![0u8; u16::MAX as usize + 1];
+
+        let _ = InstructionsBuilder::new().instruction(&Instruction {
+            program_id: &[1u8; 32],
+            data: &data,
+            accounts: &[],
+        });
+    }
+}
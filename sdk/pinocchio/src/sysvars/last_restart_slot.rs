@@ -0,0 +1,136 @@
+//! Information about the last restart slot (hard fork).
+//!
+//! The sysvar holds the slot of the last restart of the cluster, or `0` if the
+//! cluster has never been restarted.
+
+use super::{clock::Slot, FixedLayoutSysvar, Sysvar};
+use crate::{
+    account_info::{AccountInfo, Ref},
+    impl_sysvar_get,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The ID of the last restart slot sysvar.
+pub const LAST_RESTART_SLOT_ID: Pubkey = [
+    6, 167, 213, 23, 25, 6, 221, 225, 205, 63, 148, 125, 202, 180, 200, 244, 244, 245, 27, 173, 15,
+    152, 19, 184, 0, 210, 137, 71, 31, 192, 0, 0,
+];
+
+declare_sysvar_id!(LAST_RESTART_SLOT_ID, LastRestartSlot);
+
+/// Last restart slot sysvar data.
+///
+/// The type uses the same layout as the sysvar account data, with an alignment
+/// of 1, so the value is accessed through a method.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LastRestartSlot {
+    /// The last restart `Slot`.
+    last_restart_slot: [u8; 8],
+}
+
+impl LastRestartSlot {
+    /// The length of the `LastRestartSlot` sysvar account data.
+    pub const LEN: usize = 8;
+
+    /// Create a new `LastRestartSlot`.
+    pub fn new(last_restart_slot: Slot) -> Self {
+        Self {
+            last_restart_slot: last_restart_slot.to_le_bytes(),
+        }
+    }
+
+    /// Return a `LastRestartSlot` from the given account info.
+    ///
+    /// This method performs a check on the account info key.
+    #[inline]
+    pub fn from_account_info(
+        account_info: &AccountInfo,
+    ) -> Result<Ref<LastRestartSlot>, ProgramError> {
+        if account_info.key() != &LAST_RESTART_SLOT_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(data, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return a `LastRestartSlot` from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        if account_info.key() != &LAST_RESTART_SLOT_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Self::from_bytes(account_info.borrow_data_unchecked())
+    }
+
+    /// Return a `LastRestartSlot` from the given bytes.
+    ///
+    /// This method performs a length validation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(unsafe { Self::from_bytes_unchecked(bytes) })
+    }
+
+    /// Return a `LastRestartSlot` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` has the expected length.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes.as_ptr() as *const LastRestartSlot)
+    }
+
+    /// The last restart `Slot`.
+    #[inline(always)]
+    pub fn last_restart_slot(&self) -> Slot {
+        u64::from_le_bytes(self.last_restart_slot)
+    }
+}
+
+impl Sysvar for LastRestartSlot {
+    impl_sysvar_get!(sol_get_last_restart_slot);
+}
+
+impl FixedLayoutSysvar for LastRestartSlot {
+    const LEN: usize = Self::LEN;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes() {
+        let mut bytes = [0u8; LastRestartSlot::LEN + 1];
+        bytes[1..].copy_from_slice(&42u64.to_le_bytes());
+
+        // The data does not need to be aligned.
+        let last_restart_slot = LastRestartSlot::from_bytes(&bytes[1..]).unwrap();
+
+        assert_eq!(core::mem::align_of::<LastRestartSlot>(), 1);
+        assert_eq!(last_restart_slot.last_restart_slot(), 42);
+        assert_eq!(last_restart_slot, &LastRestartSlot::new(42));
+
+        assert!(LastRestartSlot::from_bytes(&bytes).is_err());
+    }
+}
@@ -1,11 +1,61 @@
 //! Provides access to cluster system accounts.
 
-use crate::program_error::ProgramError;
+use crate::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Declares the `ID` constant and the `check_id` function of a sysvar module.
+///
+/// When a sysvar type is provided, it also implements [`SysvarAccount`] for the
+/// type using its inherent `from_account_info` and `from_account_info_unchecked`
+/// methods.
+macro_rules! declare_sysvar_id {
+    ( $id:ident ) => {
+        /// The ID of the sysvar.
+        pub const ID: $crate::pubkey::Pubkey = $id;
+
+        /// Check whether the given `id` is the ID of the sysvar.
+        #[inline(always)]
+        pub fn check_id(id: &$crate::pubkey::Pubkey) -> bool {
+            id == &ID
+        }
+    };
+
+    ( $id:ident, $sysvar:ty ) => {
+        declare_sysvar_id!($id);
+
+        impl $crate::sysvars::SysvarAccount for $sysvar {
+            const ID: $crate::pubkey::Pubkey = $id;
+
+            #[inline(always)]
+            fn from_account_info(
+                account_info: &$crate::account_info::AccountInfo,
+            ) -> Result<$crate::account_info::Ref<Self>, $crate::program_error::ProgramError> {
+                <$sysvar>::from_account_info(account_info)
+            }
+
+            #[inline(always)]
+            unsafe fn from_account_info_unchecked(
+                account_info: &$crate::account_info::AccountInfo,
+            ) -> Result<&Self, $crate::program_error::ProgramError> {
+                <$sysvar>::from_account_info_unchecked(account_info)
+            }
+        }
+    };
+}
 
 pub mod clock;
+pub mod epoch_rewards;
+pub mod epoch_schedule;
 pub mod fees;
 pub mod instructions;
+pub mod last_restart_slot;
 pub mod rent;
+pub mod slot_hashes;
+pub mod slot_history;
+pub mod stake_history;
 
 /// A type that holds sysvar data.
 pub trait Sysvar: Default + Sized {
@@ -22,6 +72,199 @@ pub trait Sysvar: Default + Sized {
     }
 }
 
+/// A sysvar that can be loaded from its account.
+///
+/// This allows instruction handlers that receive sysvars as accounts to load them
+/// uniformly. The sysvar data is not copied: it is borrowed from the account data.
+pub trait SysvarAccount {
+    /// The ID of the sysvar.
+    const ID: Pubkey;
+
+    /// Check whether the given `id` is the ID of the sysvar.
+    #[inline(always)]
+    fn check_id(id: &Pubkey) -> bool {
+        id == &Self::ID
+    }
+
+    /// Return the sysvar from the given account info.
+    ///
+    /// This method performs a check on the account info key.
+    fn from_account_info(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError>;
+
+    /// Return the sysvar from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError>;
+}
+
+/// A sysvar with a fixed-layout account data.
+///
+/// The account data of these sysvars can be read partially using the
+/// `sol_get_sysvar` syscall, without requiring the sysvar account to be passed
+/// to the program. This is useful to read individual fields of a sysvar, since
+/// the cost of the syscall depends on the number of bytes read.
+///
+/// # Example
+///
+/// ```ignore
+/// use pinocchio::sysvars::{rent::Rent, FixedLayoutSysvar};
+///
+/// // Read the `lamports_per_byte_year` field of the `Rent` sysvar.
+/// let lamports_per_byte_year = u64::from_le_bytes(Rent::get_array::<8>(0)?);
+/// ```
+pub trait FixedLayoutSysvar: SysvarAccount {
+    /// The length of the sysvar account data.
+    const LEN: usize;
+
+    /// Copy the account data of the sysvar starting at `offset` into `buffer`.
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if the range is not within the
+    /// sysvar account data.
+    #[inline]
+    fn get_range(offset: usize, buffer: &mut [u8]) -> Result<(), ProgramError> {
+        match offset.checked_add(buffer.len()) {
+            Some(end) if end <= Self::LEN => get_sysvar_bytes(&Self::ID, offset as u64, buffer),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+
+    /// Return `N` bytes of the account data of the sysvar starting at `offset`.
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if the range is not within the
+    /// sysvar account data.
+    #[inline]
+    fn get_array<const N: usize>(offset: usize) -> Result<[u8; N], ProgramError> {
+        let mut array = [0u8; N];
+        Self::get_range(offset, &mut array)?;
+        Ok(array)
+    }
+}
+
+/// Return value of `sol_get_sysvar` indicating that the requested range is not
+/// within the sysvar account data.
+pub(crate) const OFFSET_LENGTH_EXCEEDS_SYSVAR: u64 = 1;
+
+/// Return value of `sol_get_sysvar` indicating that the sysvar was not found.
+pub(crate) const SYSVAR_NOT_FOUND: u64 = 2;
+
+/// Copy the account data of the sysvar `id` starting at `offset` into `buffer`.
+///
+/// This uses the `sol_get_sysvar` syscall, which does not require the sysvar
+/// account to be passed to the program.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the range is not within the sysvar
+/// account data, and [`ProgramError::UnsupportedSysvar`] if the sysvar is not
+/// available.
+#[inline]
+pub fn get_sysvar_bytes(id: &Pubkey, offset: u64, buffer: &mut [u8]) -> Result<(), ProgramError> {
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        crate::syscalls::sol_get_sysvar(
+            id as *const _ as *const u8,
+            buffer.as_mut_ptr(),
+            offset,
+            buffer.len() as u64,
+        )
+    };
+
+    #[cfg(not(target_os = "solana"))]
+    let result = unsafe {
+        crate::host::sol_get_sysvar(
+            id as *const _ as *const u8,
+            buffer.as_mut_ptr(),
+            offset,
+            buffer.len() as u64,
+        )
+    };
+
+    match result {
+        crate::SUCCESS => Ok(()),
+        OFFSET_LENGTH_EXCEEDS_SYSVAR => Err(ProgramError::InvalidArgument),
+        SYSVAR_NOT_FOUND => Err(ProgramError::UnsupportedSysvar),
+        e => Err(e.into()),
+    }
+}
+
+/// Return whether `bytes` is suitably aligned to be read as a `T`.
+#[inline(always)]
+fn is_aligned<T>(bytes: &[u8]) -> bool {
+    bytes.as_ptr().cast::<T>().is_aligned()
+}
+
+/// Length of the prefix holding the number of entries of sysvars whose account
+/// data is a vector of entries.
+const VEC_LEN_PREFIX: usize = 8;
+
+/// Return the number of entries of a sysvar account data holding a vector of
+/// `entry_len`-byte entries, validating that they are all present.
+#[inline(always)]
+fn vec_len(bytes: &[u8], entry_len: usize) -> Result<usize, ProgramError> {
+    let len = match bytes.get(..VEC_LEN_PREFIX) {
+        // SAFETY: The prefix is `VEC_LEN_PREFIX` bytes long.
+        Some(prefix) => u64::from_le_bytes(unsafe { *(prefix.as_ptr() as *const [u8; 8]) }),
+        None => return Err(ProgramError::InvalidAccountData),
+    };
+
+    match (len as usize)
+        .checked_mul(entry_len)
+        .and_then(|size| size.checked_add(VEC_LEN_PREFIX))
+    {
+        Some(size) if size <= bytes.len() => Ok(len as usize),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Read the number of entries of the sysvar `id`, whose account data holds a
+/// vector of entries.
+#[inline(always)]
+fn fetch_vec_len(id: &Pubkey) -> Result<usize, ProgramError> {
+    let mut len = [0u8; VEC_LEN_PREFIX];
+    get_sysvar_bytes(id, 0, &mut len)?;
+    Ok(u64::from_le_bytes(len) as usize)
+}
+
+/// Read the index of the entry with the given `key` of the sysvar `id`, whose
+/// account data holds a vector of `entry_len`-byte entries sorted in descending
+/// order by a leading `u64` key.
+///
+/// The binary search reads only the key of the visited entries.
+fn fetch_vec_position(
+    id: &Pubkey,
+    entry_len: usize,
+    key: u64,
+) -> Result<Option<usize>, ProgramError> {
+    let mut low = 0;
+    let mut high = fetch_vec_len(id)?;
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+
+        let mut entry_key = [0u8; 8];
+        get_sysvar_bytes(
+            id,
+            (VEC_LEN_PREFIX + middle * entry_len) as u64,
+            &mut entry_key,
+        )?;
+
+        match key.cmp(&u64::from_le_bytes(entry_key)) {
+            core::cmp::Ordering::Equal => return Ok(Some(middle)),
+            // keys are in descending order
+            core::cmp::Ordering::Greater => high = middle,
+            core::cmp::Ordering::Less => low = middle + 1,
+        }
+    }
+
+    Ok(None)
+}
+
 /// Implements the [`Sysvar::get`] method for both SBF and host targets.
 #[macro_export]
 macro_rules! impl_sysvar_get {
@@ -34,7 +277,7 @@ macro_rules! impl_sysvar_get {
             let result = unsafe { $crate::syscalls::$syscall_name(var_addr) };
 
             #[cfg(not(target_os = "solana"))]
-            let result = core::hint::black_box(var_addr as *const _ as u64);
+            let result = unsafe { $crate::host::$syscall_name(var_addr) };
 
             match result {
                 $crate::SUCCESS => Ok(var),
@@ -43,3 +286,117 @@ macro_rules! impl_sysvar_get {
         }
     };
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    extern crate std;
+
+    use core::mem::MaybeUninit;
+
+    use super::{clock::Clock, fees::FeeCalculator, get_sysvar_bytes, rent::Rent, SysvarAccount};
+    use crate::{
+        account_info::AccountInfo,
+        entrypoint::{deserialize, InputAccount, InputBuilder},
+        host::{set_backend, TestBackend},
+        program_error::ProgramError,
+    };
+    use std::{rc::Rc, vec};
+
+    #[test]
+    fn test_sysvar_account() {
+        let mut clock = [0u8; Clock::LEN];
+        clock[..8].copy_from_slice(&42u64.to_le_bytes());
+
+        let mut input = InputBuilder::new(&[1u8; 32])
+            .account(InputAccount::new(super::clock::ID, [0u8; 32]).data(&clock))
+            .account(InputAccount::new(super::rent::ID, [0u8; 32]).data(&[0u8; Rent::LEN]))
+            .account(InputAccount::new(super::fees::ID, [0u8; 32]).data(&5_000u64.to_le_bytes()))
+            .account(InputAccount::new(super::rent::ID, [0u8; 32]).data(&[0u8; 8]))
+            .build();
+
+        const UNINIT: MaybeUninit<AccountInfo> = MaybeUninit::<AccountInfo>::uninit();
+        let mut accounts = [UNINIT; 4];
+        let (_, count, _) = unsafe { deserialize::<4>(input.as_mut_ptr(), &mut accounts) };
+        assert_eq!(count, 4);
+
+        let clock_account = unsafe { accounts[0].assume_init_ref() };
+        let rent_account = unsafe { accounts[1].assume_init_ref() };
+        let fees_account = unsafe { accounts[2].assume_init_ref() };
+        let bad_rent_account = unsafe { accounts[3].assume_init_ref() };
+
+        assert!(Clock::check_id(clock_account.key()));
+        assert!(super::clock::check_id(clock_account.key()));
+        assert!(!Clock::check_id(rent_account.key()));
+
+        let clock = <Clock as SysvarAccount>::from_account_info(clock_account).unwrap();
+        assert_eq!(clock.slot, 42);
+        drop(clock);
+
+        assert!(<Rent as SysvarAccount>::from_account_info(rent_account).is_ok());
+        assert_eq!(
+            <Rent as SysvarAccount>::from_account_info(clock_account).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            unsafe { <Rent as SysvarAccount>::from_account_info_unchecked(bad_rent_account) }.err(),
+            Some(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            <Rent as SysvarAccount>::from_account_info(bad_rent_account).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+
+        let fee_calculator =
+            <FeeCalculator as SysvarAccount>::from_account_info(fees_account).unwrap();
+        assert_eq!(fee_calculator.lamports_per_signature, 5_000);
+    }
+
+    #[test]
+    fn test_from_bytes_alignment() {
+        let buffer = [0u8; Clock::LEN + 8];
+        let offset = buffer.as_ptr().align_offset(core::mem::align_of::<Clock>());
+
+        assert!(Clock::from_bytes(&buffer[offset..offset + Clock::LEN]).is_ok());
+        assert_eq!(
+            Clock::from_bytes(&buffer[offset + 1..offset + 1 + Clock::LEN]).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            FeeCalculator::from_bytes(&buffer[offset + 1..offset + 1 + FeeCalculator::LEN]).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+        assert!(Rent::from_bytes(&buffer[offset..offset + Rent::LEN]).is_ok());
+        assert_eq!(
+            Rent::from_bytes(&buffer[offset + 1..offset + 1 + Rent::LEN]).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_get_sysvar_bytes() {
+        let id = [9u8; 32];
+        let mut buffer = [0u8; 4];
+
+        assert_eq!(
+            get_sysvar_bytes(&id, 0, &mut buffer).err(),
+            Some(ProgramError::UnsupportedSysvar)
+        );
+
+        let backend = Rc::new(TestBackend {
+            sysvar_data: vec![(id, (0..16).collect())],
+            ..TestBackend::default()
+        });
+        let _guard = set_backend(backend);
+
+        get_sysvar_bytes(&id, 12, &mut buffer).unwrap();
+        assert_eq!(buffer, [12, 13, 14, 15]);
+        assert_eq!(
+            get_sysvar_bytes(&id, 13, &mut buffer).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            get_sysvar_bytes(&id, u64::MAX, &mut buffer).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This is required for the rent sysvar implementation.
 
-use super::Sysvar;
+use super::{is_aligned, FixedLayoutSysvar, Sysvar};
 use crate::{
     account_info::{AccountInfo, Ref},
     impl_sysvar_get,
@@ -16,6 +16,8 @@ pub const RENT_ID: Pubkey = [
     253, 68, 227, 219, 217, 138, 0, 0, 0, 0,
 ];
 
+declare_sysvar_id!(RENT_ID, Rent);
+
 /// Default rental rate in lamports/byte-year.
 ///
 /// This calculation is based on:
@@ -76,7 +78,11 @@ impl Rent {
         if account_info.key() != &RENT_ID {
             return Err(ProgramError::InvalidArgument);
         }
-        Ok(Ref::map(account_info.try_borrow_data()?, |data| unsafe {
+        let data = account_info.try_borrow_data()?;
+        if data.len() != Self::LEN || !is_aligned::<Self>(&data) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(data, |data| unsafe {
             Self::from_bytes_unchecked(data)
         }))
     }
@@ -97,18 +103,15 @@ impl Rent {
         if account_info.key() != &RENT_ID {
             return Err(ProgramError::InvalidArgument);
         }
-        Ok(Self::from_bytes_unchecked(
-            account_info.borrow_data_unchecked(),
-        ))
+        Self::from_bytes(account_info.borrow_data_unchecked())
     }
 
     /// Return a `Rent` from the given bytes.
     ///
-    /// This method performs a length validation. The caller must ensure that `bytes` contains
-    /// a valid representation of `Rent`.
+    /// This method performs a length and alignment validation.
     #[inline]
     pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
-        if bytes.len() != Self::LEN {
+        if bytes.len() != Self::LEN || !is_aligned::<Self>(bytes) {
             return Err(ProgramError::InvalidArgument);
         }
         Ok(unsafe { Self::from_bytes_unchecked(bytes) })
@@ -118,8 +121,8 @@ impl Rent {
     ///
     /// # Safety
     ///
-    /// The caller must ensure that `bytes` contains a valid representation of `Rent` and
-    /// that is has the expected length.
+    /// The caller must ensure that `bytes` has the expected length and is
+    /// suitably aligned.
     #[inline]
     pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
         &*(bytes.as_ptr() as *const Rent)
@@ -207,6 +210,10 @@ impl Sysvar for Rent {
     impl_sysvar_get!(sol_get_rent_sysvar);
 }
 
+impl FixedLayoutSysvar for Rent {
+    const LEN: usize = Self::LEN;
+}
+
 /// The return value of [`Rent::due`].
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum RentDue {
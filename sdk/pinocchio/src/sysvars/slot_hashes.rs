@@ -0,0 +1,312 @@
+//! Hashes of the most recent slots.
+//!
+//! The sysvar holds up to [`MAX_ENTRIES`] `(slot, hash)` entries, ordered from
+//! the most recent slot to the oldest one. Since the account data is large
+//! (about 20KB), [`SlotHashes`] provides a zero-copy view over the account data,
+//! as well as methods to read individual entries through the `sol_get_sysvar`
+//! syscall without requiring the sysvar account.
+
+use super::{
+    clock::Slot, fetch_vec_len, fetch_vec_position, get_sysvar_bytes, vec_len, VEC_LEN_PREFIX,
+};
+use crate::{
+    account_info::{AccountInfo, Ref},
+    hash::Hash,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The ID of the slot hashes sysvar.
+pub const SLOT_HASHES_ID: Pubkey = [
+    6, 167, 213, 23, 25, 47, 10, 175, 198, 242, 101, 227, 251, 119, 204, 122, 218, 130, 197, 41,
+    208, 190, 59, 19, 110, 45, 0, 85, 32, 0, 0, 0,
+];
+
+declare_sysvar_id!(SLOT_HASHES_ID, SlotHashes);
+
+/// The maximum number of entries of the slot hashes sysvar.
+pub const MAX_ENTRIES: usize = 512;
+
+/// A `(slot, hash)` entry of the slot hashes sysvar.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SlotHashEntry {
+    /// The slot of the entry.
+    slot: [u8; 8],
+
+    /// The bank hash of the slot.
+    hash: Hash,
+}
+
+impl SlotHashEntry {
+    /// The length of an entry.
+    pub const LEN: usize = 8 + 32;
+
+    /// Create a new `SlotHashEntry`.
+    #[inline(always)]
+    pub fn new(slot: Slot, hash: Hash) -> Self {
+        Self {
+            slot: slot.to_le_bytes(),
+            hash,
+        }
+    }
+
+    /// The slot of the entry.
+    #[inline(always)]
+    pub fn slot(&self) -> Slot {
+        u64::from_le_bytes(self.slot)
+    }
+
+    /// The bank hash of the slot.
+    #[inline(always)]
+    pub fn hash(&self) -> &Hash {
+        &self.hash
+    }
+}
+
+/// Slot hashes sysvar data.
+///
+/// This is a zero-copy view over the account data of the sysvar, where entries are
+/// sorted by slot in descending order.
+#[repr(C)]
+#[derive(Debug)]
+pub struct SlotHashes {
+    /// Number of entries.
+    len: [u8; 8],
+
+    /// The entries, from the most recent slot to the oldest one.
+    entries: [SlotHashEntry],
+}
+
+impl SlotHashes {
+    /// The length of the `SlotHashes` sysvar account data.
+    pub const LEN: usize = VEC_LEN_PREFIX + MAX_ENTRIES * SlotHashEntry::LEN;
+
+    /// Return a `SlotHashes` from the given account info.
+    ///
+    /// This method performs a check on the account info key.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<SlotHashes>, ProgramError> {
+        if account_info.key() != &SLOT_HASHES_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        let len = vec_len(&data, SlotHashEntry::LEN)?;
+        Ok(Ref::map(data, |data| unsafe {
+            Self::from_bytes_unchecked(data, len)
+        }))
+    }
+
+    /// Return a `SlotHashes` from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        if account_info.key() != &SLOT_HASHES_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Self::from_bytes(account_info.borrow_data_unchecked())
+    }
+
+    /// Return a `SlotHashes` from the given bytes.
+    ///
+    /// This method validates that `bytes` contains the number of entries in its
+    /// prefix.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        let len = vec_len(bytes, SlotHashEntry::LEN)?;
+        Ok(unsafe { Self::from_bytes_unchecked(bytes, len) })
+    }
+
+    /// Return a `SlotHashes` with `len` entries from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` contains at least `len` entries after
+    /// the length prefix.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8], len: usize) -> &Self {
+        &*(core::ptr::slice_from_raw_parts(bytes.as_ptr(), len) as *const SlotHashes)
+    }
+
+    /// Return the number of entries.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return whether there are no entries.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return the entries, from the most recent slot to the oldest one.
+    #[inline(always)]
+    pub fn entries(&self) -> &[SlotHashEntry] {
+        &self.entries
+    }
+
+    /// Return the entry at the given index, if any.
+    #[inline(always)]
+    pub fn get_entry(&self, index: usize) -> Option<&SlotHashEntry> {
+        self.entries.get(index)
+    }
+
+    /// Return the index of the entry of the given slot, if any.
+    ///
+    /// This performs a binary search on the entries.
+    #[inline]
+    pub fn position(&self, slot: Slot) -> Option<usize> {
+        self.entries
+            .binary_search_by(|entry| slot.cmp(&entry.slot()))
+            .ok()
+    }
+
+    /// Return the hash of the given slot, if any.
+    #[inline]
+    pub fn get(&self, slot: Slot) -> Option<&Hash> {
+        self.position(slot).map(|index| self.entries[index].hash())
+    }
+
+    /// Read the number of entries of the sysvar.
+    ///
+    /// This uses the `sol_get_sysvar` syscall, so the sysvar account is not required.
+    #[inline]
+    pub fn fetch_len() -> Result<usize, ProgramError> {
+        fetch_vec_len(&SLOT_HASHES_ID)
+    }
+
+    /// Read the entry at the given index of the sysvar, if any.
+    ///
+    /// This uses the `sol_get_sysvar` syscall, so the sysvar account is not required.
+    #[inline]
+    pub fn fetch_entry(index: usize) -> Result<Option<SlotHashEntry>, ProgramError> {
+        if index >= Self::fetch_len()? {
+            return Ok(None);
+        }
+        Self::fetch_entry_unchecked(index).map(Some)
+    }
+
+    /// Read the index of the entry of the given slot of the sysvar, if any.
+    ///
+    /// This uses the `sol_get_sysvar` syscall to perform a binary search on the
+    /// entries, reading only the slot of the visited entries.
+    #[inline]
+    pub fn fetch_position(slot: Slot) -> Result<Option<usize>, ProgramError> {
+        fetch_vec_position(&SLOT_HASHES_ID, SlotHashEntry::LEN, slot)
+    }
+
+    /// Read the hash of the given slot of the sysvar, if any.
+    ///
+    /// This uses the `sol_get_sysvar` syscall to perform a binary search on the
+    /// entries, so the sysvar account is not required.
+    #[inline]
+    pub fn fetch(slot: Slot) -> Result<Option<Hash>, ProgramError> {
+        match Self::fetch_position(slot)? {
+            Some(index) => Ok(Some(*Self::fetch_entry_unchecked(index)?.hash())),
+            None => Ok(None),
+        }
+    }
+
+    /// Read the entry at the given index of the sysvar without checking the
+    /// number of entries.
+    #[inline(always)]
+    fn fetch_entry_unchecked(index: usize) -> Result<SlotHashEntry, ProgramError> {
+        let mut entry = SlotHashEntry::default();
+        // SAFETY: `SlotHashEntry` is a `repr(C)` struct of byte arrays.
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(
+                &mut entry as *mut SlotHashEntry as *mut u8,
+                SlotHashEntry::LEN,
+            )
+        };
+        get_sysvar_bytes(
+            &SLOT_HASHES_ID,
+            (VEC_LEN_PREFIX + index * SlotHashEntry::LEN) as u64,
+            bytes,
+        )?;
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_data(slots: &[Slot]) -> [u8; SlotHashes::LEN] {
+        let mut data = [0u8; SlotHashes::LEN];
+        data[..VEC_LEN_PREFIX].copy_from_slice(&(slots.len() as u64).to_le_bytes());
+        for (entry, slot) in data[VEC_LEN_PREFIX..]
+            .chunks_exact_mut(SlotHashEntry::LEN)
+            .zip(slots)
+        {
+            entry[..8].copy_from_slice(&slot.to_le_bytes());
+            entry[8..].copy_from_slice(&[*slot as u8; 32]);
+        }
+        data
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let data = account_data(&[20, 18, 17, 15, 10]);
+        let slot_hashes = SlotHashes::from_bytes(&data).unwrap();
+
+        assert_eq!(slot_hashes.len(), 5);
+        assert_eq!(slot_hashes.get_entry(1).unwrap().slot(), 18);
+        assert!(slot_hashes.get_entry(5).is_none());
+
+        assert_eq!(slot_hashes.position(20), Some(0));
+        assert_eq!(slot_hashes.position(15), Some(3));
+        assert_eq!(slot_hashes.position(10), Some(4));
+        assert_eq!(slot_hashes.position(16), None);
+        assert_eq!(slot_hashes.position(21), None);
+        assert_eq!(slot_hashes.position(9), None);
+        assert_eq!(slot_hashes.get(17), Some(&Hash::new([17; 32])));
+
+        let empty = account_data(&[]);
+        assert!(SlotHashes::from_bytes(&empty).unwrap().is_empty());
+
+        // missing entries
+        let len = VEC_LEN_PREFIX + 5 * SlotHashEntry::LEN;
+        assert!(SlotHashes::from_bytes(&data[..len]).is_ok());
+        assert!(SlotHashes::from_bytes(&data[..len - 1]).is_err());
+        assert!(SlotHashes::from_bytes(&data[..4]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_fetch() {
+        extern crate std;
+
+        use crate::host::{set_backend, TestBackend};
+        use std::{rc::Rc, vec};
+
+        let data = account_data(&[20, 18, 17, 15, 10]);
+
+        let backend = Rc::new(TestBackend {
+            sysvar_data: vec![(SLOT_HASHES_ID, data.to_vec())],
+            ..TestBackend::default()
+        });
+        let _guard = set_backend(backend);
+
+        assert_eq!(SlotHashes::fetch_len().unwrap(), 5);
+        assert_eq!(SlotHashes::fetch_entry(4).unwrap().unwrap().slot(), 10);
+        assert!(SlotHashes::fetch_entry(5).unwrap().is_none());
+
+        for (index, slot) in [20, 18, 17, 15, 10].into_iter().enumerate() {
+            assert_eq!(SlotHashes::fetch_position(slot).unwrap(), Some(index));
+        }
+        assert_eq!(SlotHashes::fetch_position(19).unwrap(), None);
+        assert_eq!(SlotHashes::fetch(15).unwrap(), Some(Hash::new([15; 32])));
+        assert_eq!(SlotHashes::fetch(11).unwrap(), None);
+    }
+}
@@ -0,0 +1,291 @@
+//! History of the slots that were rooted.
+//!
+//! The sysvar holds a bit vector of [`MAX_ENTRIES`] bits, where the bit of a slot
+//! is set if the slot was rooted. The bit of `slot` is at index
+//! `slot % MAX_ENTRIES`, so only the most recent [`MAX_ENTRIES`] slots are
+//! tracked.
+//!
+//! Since the account data is large (about 128KB), the history is usually read
+//! through the `sol_get_sysvar` syscall with [`SlotHistory::fetch_check`], which
+//! only reads the next slot and the block of bits of the requested slot.
+
+use super::{clock::Slot, FixedLayoutSysvar};
+use crate::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The ID of the slot history sysvar.
+pub const SLOT_HISTORY_ID: Pubkey = [
+    6, 167, 213, 23, 25, 47, 10, 175, 200, 117, 226, 225, 132, 87, 124, 80, 105, 207, 200, 70, 73,
+    227, 235, 146, 120, 47, 149, 141, 72, 0, 0, 0,
+];
+
+declare_sysvar_id!(SLOT_HISTORY_ID, SlotHistory);
+
+/// The number of slots tracked by the slot history sysvar.
+pub const MAX_ENTRIES: u64 = 1024 * 1024;
+
+/// Number of bits in a block of the bit vector.
+const BITS_PER_BLOCK: u64 = 64;
+
+/// Number of blocks of the bit vector.
+const BLOCKS: usize = (MAX_ENTRIES / BITS_PER_BLOCK) as usize;
+
+/// Offset of the first block of the bit vector in the account data.
+///
+/// The blocks are preceded by the `Option` tag and the number of blocks.
+const BLOCKS_OFFSET: usize = 1 + 8;
+
+/// Offset of the next slot in the account data.
+///
+/// The next slot is preceded by the blocks and the number of bits.
+const NEXT_SLOT_OFFSET: usize = BLOCKS_OFFSET + BLOCKS * 8 + 8;
+
+/// The result of checking whether a slot is present in the slot history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// The slot is newer than the most recent slot of the history.
+    Future,
+
+    /// The slot is older than the oldest slot tracked by the history.
+    TooOld,
+
+    /// The slot was rooted.
+    Found,
+
+    /// The slot was not rooted.
+    NotFound,
+}
+
+/// Slot history sysvar data.
+///
+/// The type uses the same layout as the sysvar account data, where fields are not
+/// aligned, so the values are accessed through methods.
+#[repr(C)]
+pub struct SlotHistory {
+    /// `Option` tag of the bit vector blocks.
+    blocks_tag: u8,
+
+    /// Number of blocks of the bit vector.
+    blocks_len: [u8; 8],
+
+    /// Blocks of the bit vector.
+    blocks: [[u8; 8]; BLOCKS],
+
+    /// Number of bits of the bit vector.
+    bits_len: [u8; 8],
+
+    /// The slot after the most recent slot of the history.
+    next_slot: [u8; 8],
+}
+
+impl SlotHistory {
+    /// The length of the `SlotHistory` sysvar account data.
+    pub const LEN: usize = NEXT_SLOT_OFFSET + 8;
+
+    /// Return a `SlotHistory` from the given account info.
+    ///
+    /// This method performs a check on the account info key.
+    #[inline]
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Ref<SlotHistory>, ProgramError> {
+        if account_info.key() != &SLOT_HISTORY_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(data, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return a `SlotHistory` from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        if account_info.key() != &SLOT_HISTORY_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Self::from_bytes(account_info.borrow_data_unchecked())
+    }
+
+    /// Return a `SlotHistory` from the given bytes.
+    ///
+    /// This method performs a length validation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(unsafe { Self::from_bytes_unchecked(bytes) })
+    }
+
+    /// Return a `SlotHistory` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` has the expected length.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes.as_ptr() as *const SlotHistory)
+    }
+
+    /// The slot after the most recent slot of the history.
+    #[inline(always)]
+    pub fn next_slot(&self) -> Slot {
+        u64::from_le_bytes(self.next_slot)
+    }
+
+    /// The most recent slot of the history.
+    #[inline(always)]
+    pub fn newest(&self) -> Slot {
+        self.next_slot().saturating_sub(1)
+    }
+
+    /// The oldest slot tracked by the history.
+    #[inline(always)]
+    pub fn oldest(&self) -> Slot {
+        self.next_slot().saturating_sub(MAX_ENTRIES)
+    }
+
+    /// Check whether the given slot was rooted.
+    #[inline]
+    pub fn check(&self, slot: Slot) -> Check {
+        match bit_index(slot, self.next_slot()) {
+            Ok(index) => check_bit(self.blocks[(index / BITS_PER_BLOCK) as usize], index),
+            Err(check) => check,
+        }
+    }
+
+    /// Read the slot after the most recent slot of the sysvar.
+    ///
+    /// This uses the `sol_get_sysvar` syscall, so the sysvar account is not required.
+    #[inline]
+    pub fn fetch_next_slot() -> Result<Slot, ProgramError> {
+        Self::get_array(NEXT_SLOT_OFFSET).map(u64::from_le_bytes)
+    }
+
+    /// Check whether the given slot was rooted, reading the sysvar.
+    ///
+    /// This uses the `sol_get_sysvar` syscall to read the next slot and, if the slot
+    /// is tracked by the history, the block of bits of the slot. The sysvar account
+    /// is not required.
+    pub fn fetch_check(slot: Slot) -> Result<Check, ProgramError> {
+        match bit_index(slot, Self::fetch_next_slot()?) {
+            Ok(index) => {
+                let block = Self::get_array(BLOCKS_OFFSET + (index / BITS_PER_BLOCK) as usize * 8)?;
+                Ok(check_bit(block, index))
+            }
+            Err(check) => Ok(check),
+        }
+    }
+}
+
+impl FixedLayoutSysvar for SlotHistory {
+    const LEN: usize = Self::LEN;
+}
+
+/// Return the index of the bit of `slot` given the `next_slot` of the history, or
+/// the result of the check if the slot is not tracked by the history.
+#[inline(always)]
+fn bit_index(slot: Slot, next_slot: Slot) -> Result<u64, Check> {
+    if slot >= next_slot {
+        Err(Check::Future)
+    } else if slot < next_slot.saturating_sub(MAX_ENTRIES) {
+        Err(Check::TooOld)
+    } else {
+        Ok(slot % MAX_ENTRIES)
+    }
+}
+
+/// Check whether the bit at `index` is set in the block of bits that contains it.
+#[inline(always)]
+fn check_bit(block: [u8; 8], index: u64) -> Check {
+    if u64::from_le_bytes(block) & (1 << (index % BITS_PER_BLOCK)) != 0 {
+        Check::Found
+    } else {
+        Check::NotFound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the account data of a slot history with the given rooted slots.
+    fn account_data(slots: &[Slot], next_slot: Slot) -> [u8; SlotHistory::LEN] {
+        let mut data = [0u8; SlotHistory::LEN];
+        data[0] = 1;
+        data[1..BLOCKS_OFFSET].copy_from_slice(&(BLOCKS as u64).to_le_bytes());
+        data[NEXT_SLOT_OFFSET - 8..NEXT_SLOT_OFFSET].copy_from_slice(&MAX_ENTRIES.to_le_bytes());
+        data[NEXT_SLOT_OFFSET..].copy_from_slice(&next_slot.to_le_bytes());
+
+        for slot in slots {
+            let index = slot % MAX_ENTRIES;
+            data[BLOCKS_OFFSET + (index / 8) as usize] |= 1 << (index % 8);
+        }
+
+        data
+    }
+
+    #[test]
+    fn test_check() {
+        let next_slot = MAX_ENTRIES + 10;
+        let data = account_data(&[11, 64, MAX_ENTRIES + 9], next_slot);
+        let slot_history = SlotHistory::from_bytes(&data).unwrap();
+
+        assert_eq!(slot_history.newest(), MAX_ENTRIES + 9);
+        assert_eq!(slot_history.oldest(), 10);
+
+        assert_eq!(slot_history.check(MAX_ENTRIES + 10), Check::Future);
+        assert_eq!(slot_history.check(MAX_ENTRIES + 9), Check::Found);
+        assert_eq!(slot_history.check(MAX_ENTRIES + 8), Check::NotFound);
+        assert_eq!(slot_history.check(64), Check::Found);
+        assert_eq!(slot_history.check(63), Check::NotFound);
+        assert_eq!(slot_history.check(11), Check::Found);
+        assert_eq!(slot_history.check(10), Check::NotFound);
+        assert_eq!(slot_history.check(9), Check::TooOld);
+
+        assert!(SlotHistory::from_bytes(&data[1..]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_fetch_check() {
+        extern crate std;
+
+        use crate::host::{set_backend, TestBackend};
+        use std::{rc::Rc, vec};
+
+        assert_eq!(
+            SlotHistory::fetch_check(1).err(),
+            Some(ProgramError::UnsupportedSysvar)
+        );
+
+        let data = account_data(&[0, 1, 5, 200], 201);
+
+        let backend = Rc::new(TestBackend {
+            sysvar_data: vec![(SLOT_HISTORY_ID, data.to_vec())],
+            ..TestBackend::default()
+        });
+        let _guard = set_backend(backend);
+
+        assert_eq!(SlotHistory::fetch_next_slot().unwrap(), 201);
+        assert_eq!(SlotHistory::fetch_check(5).unwrap(), Check::Found);
+        assert_eq!(SlotHistory::fetch_check(6).unwrap(), Check::NotFound);
+        assert_eq!(SlotHistory::fetch_check(200).unwrap(), Check::Found);
+        assert_eq!(SlotHistory::fetch_check(201).unwrap(), Check::Future);
+    }
+}
@@ -0,0 +1,341 @@
+//! History of stake activations and deactivations.
+//!
+//! The sysvar holds up to [`MAX_ENTRIES`] entries with the effective, activating
+//! and deactivating stake of each epoch, ordered from the most recent epoch to
+//! the oldest one. [`StakeHistory`] provides a zero-copy view over the account
+//! data, as well as methods to read individual entries through the
+//! `sol_get_sysvar` syscall without requiring the sysvar account.
+
+use super::{
+    clock::Epoch, fetch_vec_len, fetch_vec_position, get_sysvar_bytes, vec_len, VEC_LEN_PREFIX,
+};
+use crate::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The ID of the stake history sysvar.
+pub const STAKE_HISTORY_ID: Pubkey = [
+    6, 167, 213, 23, 25, 53, 132, 208, 254, 237, 155, 179, 67, 29, 19, 32, 107, 229, 68, 40, 27,
+    87, 184, 86, 108, 197, 55, 95, 244, 0, 0, 0,
+];
+
+declare_sysvar_id!(STAKE_HISTORY_ID, StakeHistory);
+
+/// The maximum number of entries of the stake history sysvar.
+pub const MAX_ENTRIES: usize = 512;
+
+/// The stake history of an epoch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StakeHistoryEntry {
+    /// Effective stake at this epoch.
+    effective: [u8; 8],
+
+    /// Sum of portion of activations at this epoch.
+    activating: [u8; 8],
+
+    /// Sum of portion of deactivations at this epoch.
+    deactivating: [u8; 8],
+}
+
+impl StakeHistoryEntry {
+    /// The length of a `StakeHistoryEntry`.
+    pub const LEN: usize = 8 + 8 + 8;
+
+    /// Create a new `StakeHistoryEntry`.
+    #[inline(always)]
+    pub fn new(effective: u64, activating: u64, deactivating: u64) -> Self {
+        Self {
+            effective: effective.to_le_bytes(),
+            activating: activating.to_le_bytes(),
+            deactivating: deactivating.to_le_bytes(),
+        }
+    }
+
+    /// Effective stake at this epoch, in lamports.
+    #[inline(always)]
+    pub fn effective(&self) -> u64 {
+        u64::from_le_bytes(self.effective)
+    }
+
+    /// Sum of portion of activations at this epoch, in lamports.
+    #[inline(always)]
+    pub fn activating(&self) -> u64 {
+        u64::from_le_bytes(self.activating)
+    }
+
+    /// Sum of portion of deactivations at this epoch, in lamports.
+    #[inline(always)]
+    pub fn deactivating(&self) -> u64 {
+        u64::from_le_bytes(self.deactivating)
+    }
+}
+
+/// An `(epoch, entry)` pair of the stake history sysvar.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EpochStakeHistoryEntry {
+    /// The epoch of the entry.
+    epoch: [u8; 8],
+
+    /// The stake history of the epoch.
+    entry: StakeHistoryEntry,
+}
+
+impl EpochStakeHistoryEntry {
+    /// The length of an `EpochStakeHistoryEntry`.
+    pub const LEN: usize = 8 + StakeHistoryEntry::LEN;
+
+    /// Create a new `EpochStakeHistoryEntry`.
+    #[inline(always)]
+    pub fn new(epoch: Epoch, entry: StakeHistoryEntry) -> Self {
+        Self {
+            epoch: epoch.to_le_bytes(),
+            entry,
+        }
+    }
+
+    /// The epoch of the entry.
+    #[inline(always)]
+    pub fn epoch(&self) -> Epoch {
+        u64::from_le_bytes(self.epoch)
+    }
+
+    /// The stake history of the epoch.
+    #[inline(always)]
+    pub fn entry(&self) -> &StakeHistoryEntry {
+        &self.entry
+    }
+}
+
+/// Stake history sysvar data.
+///
+/// This is a zero-copy view over the account data of the sysvar, where entries are
+/// sorted by epoch in descending order.
+#[repr(C)]
+#[derive(Debug)]
+pub struct StakeHistory {
+    /// Number of entries.
+    len: [u8; 8],
+
+    /// The entries, from the most recent epoch to the oldest one.
+    entries: [EpochStakeHistoryEntry],
+}
+
+impl StakeHistory {
+    /// The length of the `StakeHistory` sysvar account data.
+    pub const LEN: usize = VEC_LEN_PREFIX + MAX_ENTRIES * EpochStakeHistoryEntry::LEN;
+
+    /// Return a `StakeHistory` from the given account info.
+    ///
+    /// This method performs a check on the account info key.
+    #[inline]
+    pub fn from_account_info(
+        account_info: &AccountInfo,
+    ) -> Result<Ref<StakeHistory>, ProgramError> {
+        if account_info.key() != &STAKE_HISTORY_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        let len = vec_len(&data, EpochStakeHistoryEntry::LEN)?;
+        Ok(Ref::map(data, |data| unsafe {
+            Self::from_bytes_unchecked(data, len)
+        }))
+    }
+
+    /// Return a `StakeHistory` from the given account info.
+    ///
+    /// This method performs a check on the account info key, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data – e.g., there are
+    /// no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_info_unchecked(
+        account_info: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        if account_info.key() != &STAKE_HISTORY_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Self::from_bytes(account_info.borrow_data_unchecked())
+    }
+
+    /// Return a `StakeHistory` from the given bytes.
+    ///
+    /// This method validates that `bytes` contains the number of entries in its
+    /// prefix.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        let len = vec_len(bytes, EpochStakeHistoryEntry::LEN)?;
+        Ok(unsafe { Self::from_bytes_unchecked(bytes, len) })
+    }
+
+    /// Return a `StakeHistory` with `len` entries from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` contains at least `len` entries after
+    /// the length prefix.
+    #[inline(always)]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8], len: usize) -> &Self {
+        &*(core::ptr::slice_from_raw_parts(bytes.as_ptr(), len) as *const StakeHistory)
+    }
+
+    /// Return the number of entries.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return whether there are no entries.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return the entries, from the most recent epoch to the oldest one.
+    #[inline(always)]
+    pub fn entries(&self) -> &[EpochStakeHistoryEntry] {
+        &self.entries
+    }
+
+    /// Return the index of the entry of the given epoch, if any.
+    ///
+    /// This performs a binary search on the entries.
+    #[inline]
+    pub fn position(&self, epoch: Epoch) -> Option<usize> {
+        self.entries
+            .binary_search_by(|entry| epoch.cmp(&entry.epoch()))
+            .ok()
+    }
+
+    /// Return the stake history of the given epoch, if any.
+    #[inline]
+    pub fn get(&self, epoch: Epoch) -> Option<&StakeHistoryEntry> {
+        self.position(epoch)
+            .map(|index| self.entries[index].entry())
+    }
+
+    /// Read the number of entries of the sysvar.
+    ///
+    /// This uses the `sol_get_sysvar` syscall, so the sysvar account is not required.
+    #[inline]
+    pub fn fetch_len() -> Result<usize, ProgramError> {
+        fetch_vec_len(&STAKE_HISTORY_ID)
+    }
+
+    /// Read the index of the entry of the given epoch of the sysvar, if any.
+    ///
+    /// This uses the `sol_get_sysvar` syscall to perform a binary search on the
+    /// entries, reading only the epoch of the visited entries.
+    #[inline]
+    pub fn fetch_position(epoch: Epoch) -> Result<Option<usize>, ProgramError> {
+        fetch_vec_position(&STAKE_HISTORY_ID, EpochStakeHistoryEntry::LEN, epoch)
+    }
+
+    /// Read the stake history of the given epoch of the sysvar, if any.
+    ///
+    /// This uses the `sol_get_sysvar` syscall to perform a binary search on the
+    /// entries, so the sysvar account is not required.
+    pub fn fetch(epoch: Epoch) -> Result<Option<StakeHistoryEntry>, ProgramError> {
+        let index = match Self::fetch_position(epoch)? {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        let mut entry = StakeHistoryEntry::default();
+        // SAFETY: `StakeHistoryEntry` is a `repr(C)` struct of byte arrays.
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(
+                &mut entry as *mut StakeHistoryEntry as *mut u8,
+                StakeHistoryEntry::LEN,
+            )
+        };
+        get_sysvar_bytes(
+            &STAKE_HISTORY_ID,
+            // skip the epoch of the entry
+            (VEC_LEN_PREFIX + index * EpochStakeHistoryEntry::LEN + 8) as u64,
+            bytes,
+        )?;
+
+        Ok(Some(entry))
+    }
+}
+
+impl<'a> TryFrom<&'a AccountInfo> for Ref<'a, StakeHistory> {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(account_info: &'a AccountInfo) -> Result<Self, Self::Error> {
+        StakeHistory::from_account_info(account_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_data(epochs: &[Epoch]) -> [u8; StakeHistory::LEN] {
+        let mut data = [0u8; StakeHistory::LEN];
+        data[..VEC_LEN_PREFIX].copy_from_slice(&(epochs.len() as u64).to_le_bytes());
+        for (entry, epoch) in data[VEC_LEN_PREFIX..]
+            .chunks_exact_mut(EpochStakeHistoryEntry::LEN)
+            .zip(epochs)
+        {
+            entry[..8].copy_from_slice(&epoch.to_le_bytes());
+            entry[8..16].copy_from_slice(&(epoch * 100).to_le_bytes());
+            entry[16..24].copy_from_slice(&(epoch * 10).to_le_bytes());
+            entry[24..32].copy_from_slice(&epoch.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let data = account_data(&[9, 8, 7, 5]);
+        let stake_history = StakeHistory::from_bytes(&data).unwrap();
+
+        assert_eq!(stake_history.len(), 4);
+        assert_eq!(stake_history.entries()[3].epoch(), 5);
+        assert_eq!(stake_history.position(9), Some(0));
+        assert_eq!(stake_history.position(6), None);
+        assert_eq!(stake_history.position(10), None);
+        assert_eq!(
+            stake_history.get(7),
+            Some(&StakeHistoryEntry::new(700, 70, 7))
+        );
+
+        let len = VEC_LEN_PREFIX + 4 * EpochStakeHistoryEntry::LEN;
+        assert!(StakeHistory::from_bytes(&data[..len]).is_ok());
+        assert!(StakeHistory::from_bytes(&data[..len - 1]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_fetch() {
+        extern crate std;
+
+        use crate::host::{set_backend, TestBackend};
+        use std::{rc::Rc, vec};
+
+        let data = account_data(&[9, 8, 7, 5]);
+
+        let backend = Rc::new(TestBackend {
+            sysvar_data: vec![(STAKE_HISTORY_ID, data.to_vec())],
+            ..TestBackend::default()
+        });
+        let _guard = set_backend(backend);
+
+        assert_eq!(StakeHistory::fetch_len().unwrap(), 4);
+        assert_eq!(StakeHistory::fetch_position(5).unwrap(), Some(3));
+        assert_eq!(
+            StakeHistory::fetch(8).unwrap(),
+            Some(StakeHistoryEntry::new(800, 80, 8))
+        );
+        assert_eq!(StakeHistory::fetch(6).unwrap(), None);
+    }
+}